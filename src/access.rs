@@ -0,0 +1,193 @@
+/// Which databases the server may touch, as glob patterns where `*` matches any run of
+/// characters and `?` any single character. A database is permitted when it matches an allowed
+/// pattern, or no allowed patterns are set, and matches no denied pattern. Names are matched
+/// ignoring case, as Glue stores them in lowercase
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DatabaseAccess {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl DatabaseAccess {
+    pub(crate) fn new(allowed: Vec<String>, denied: Vec<String>) -> Self {
+        Self {
+            allowed: allowed
+                .iter()
+                .map(|pattern| pattern.to_lowercase())
+                .collect(),
+            denied: denied
+                .iter()
+                .map(|pattern| pattern.to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Whether every database is permitted
+    pub(crate) fn is_unrestricted(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// Whether the server may touch the database named `database_name`
+    pub(crate) fn permits(&self, database_name: &str) -> bool {
+        let database_name = database_name.to_lowercase();
+        let matches = |pattern: &String| glob_matches(pattern, &database_name);
+        (self.allowed.is_empty() || self.allowed.iter().any(matches))
+            && !self.denied.iter().any(matches)
+    }
+}
+
+/// The database a Glue resource ARN belongs to, i.e. the database itself or the database holding
+/// a table or user-defined function, or `None` for resources outside any database, such as
+/// crawlers and jobs
+pub(crate) fn database_in_arn(arn: &str) -> Option<&str> {
+    let resource = match arn.splitn(6, ':').collect::<Vec<&str>>()[..] {
+        ["arn", _, "glue", _, _, resource] => resource,
+        _ => return None,
+    };
+    let (resource_type, path) = resource.split_once('/')?;
+    match resource_type {
+        "database" | "table" | "userDefinedFunction" => path.split('/').next(),
+        _ => None,
+    }
+}
+
+/// Whether `name` matches the glob `pattern` in full
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+    let name = name.chars().collect::<Vec<char>>();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` seen, and the position in the name it was matched from
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character and try again
+                Some((star, matched_from)) => {
+                    backtrack = Some((star, matched_from + 1));
+                    p = star + 1;
+                    n = matched_from + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn glob_matches_literal_names_in_full() {
+        assert!(glob_matches("sales", "sales"));
+        assert!(!glob_matches("sales", "sales_raw"));
+        assert!(!glob_matches("sales_raw", "sales"));
+        assert!(glob_matches("", ""));
+        assert!(!glob_matches("", "sales"));
+    }
+
+    #[test]
+    fn glob_star_matches_any_run_of_characters() {
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("*", "anything"));
+        assert!(glob_matches("analytics_*", "analytics_"));
+        assert!(glob_matches("analytics_*", "analytics_daily"));
+        assert!(glob_matches("*_pii", "customers_pii"));
+        assert!(!glob_matches("*_pii", "customers_pii_masked"));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+        assert!(glob_matches("**", "sales"));
+    }
+
+    #[test]
+    fn glob_star_backtracks_past_a_false_match() {
+        assert!(glob_matches("*ab", "aab"));
+        assert!(glob_matches("*_raw_*", "sales_raw_raw_2024"));
+        assert!(!glob_matches("*_raw_*", "sales_raw"));
+    }
+
+    #[test]
+    fn glob_question_mark_matches_exactly_one_character() {
+        assert!(glob_matches("db?", "db1"));
+        assert!(!glob_matches("db?", "db"));
+        assert!(!glob_matches("db?", "db12"));
+        assert!(glob_matches("?*", "x"));
+        assert!(!glob_matches("?*", ""));
+    }
+
+    #[test]
+    fn database_in_arn_finds_the_database_of_catalog_resources() {
+        let arn = |resource| format!("arn:aws:glue:eu-west-1:123456789012:{resource}");
+
+        assert_eq!(database_in_arn(&arn("database/sales")), Some("sales"));
+        assert_eq!(database_in_arn(&arn("table/sales/orders")), Some("sales"));
+        assert_eq!(
+            database_in_arn(&arn("userDefinedFunction/sales/to_cents")),
+            Some("sales")
+        );
+        assert_eq!(
+            database_in_arn("arn:aws-cn:glue:cn-north-1:123456789012:table/sales/orders"),
+            Some("sales")
+        );
+    }
+
+    #[test]
+    fn database_in_arn_ignores_resources_outside_databases() {
+        assert_eq!(
+            database_in_arn("arn:aws:glue:eu-west-1:123456789012:crawler/nightly"),
+            None
+        );
+        assert_eq!(
+            database_in_arn("arn:aws:glue:eu-west-1:123456789012:catalog"),
+            None
+        );
+        assert_eq!(database_in_arn("arn:aws:s3:::bucket/database/sales"), None);
+        assert_eq!(database_in_arn("sales"), None);
+    }
+
+    #[test]
+    fn unrestricted_access_permits_every_database() {
+        let access = DatabaseAccess::default();
+
+        assert!(access.is_unrestricted());
+        assert!(access.permits("anything"));
+    }
+
+    #[test]
+    fn allowed_patterns_permit_only_matching_databases() {
+        let access = DatabaseAccess::new(patterns(&["analytics_*", "shared"]), Vec::new());
+
+        assert!(!access.is_unrestricted());
+        assert!(access.permits("analytics_daily"));
+        assert!(access.permits("shared"));
+        assert!(!access.permits("raw"));
+    }
+
+    #[test]
+    fn denied_patterns_take_precedence_over_allowed_ones() {
+        let access = DatabaseAccess::new(patterns(&["analytics_*"]), patterns(&["*_pii"]));
+
+        assert!(access.permits("analytics_daily"));
+        assert!(!access.permits("analytics_pii"));
+    }
+
+    #[test]
+    fn databases_are_matched_ignoring_case() {
+        let access = DatabaseAccess::new(patterns(&["Analytics_*"]), patterns(&["*_PII"]));
+
+        assert!(access.permits("ANALYTICS_daily"));
+        assert!(!access.permits("analytics_pii"));
+    }
+}
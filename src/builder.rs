@@ -0,0 +1,270 @@
+use crate::access::DatabaseAccess;
+use crate::cache::{self, DEFAULT_CACHE_TTL};
+use crate::confirmation::Confirmations;
+use crate::{
+    AwsClients, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_RESPONSE_BYTES,
+    DEFAULT_REQUEST_TIMEOUT, GlueApi, GlueDataCatalog, RetryPolicy,
+};
+use anyhow::Context;
+use aws_config::sts::AssumeRoleProvider;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_glue::config::SharedCredentialsProvider;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio_util::task::TaskTracker;
+
+/// Configures a [`GlueDataCatalog`], loading the AWS configuration once when built. Anything not
+/// set falls back to the environment or the crate's defaults
+#[derive(Debug, Clone, Default)]
+pub struct GlueDataCatalogBuilder {
+    region: Option<String>,
+    profile_name: Option<String>,
+    endpoint_url: Option<String>,
+    assumed_role: Option<AssumedRole>,
+    cache_ttl: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    request_timeout: Option<Duration>,
+    max_concurrent_requests: Option<usize>,
+    max_response_bytes: Option<usize>,
+    case_insensitive_names: bool,
+    allowed_databases: Vec<String>,
+    denied_databases: Vec<String>,
+    allow_mutations: Option<bool>,
+    instructions: Option<String>,
+}
+
+/// The IAM role to assume for every AWS request, and the session name recorded in CloudTrail
+#[derive(Debug, Clone)]
+struct AssumedRole {
+    role_arn: String,
+    session_name: String,
+}
+
+impl GlueDataCatalogBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses the given region, ignoring any region set in the environment
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Uses the named profile from the shared AWS config and credentials files
+    pub fn profile_name(mut self, profile_name: impl Into<String>) -> Self {
+        self.profile_name = Some(profile_name.into());
+        self
+    }
+
+    /// Sends requests to `endpoint_url` instead of the real AWS endpoint, e.g. a LocalStack
+    /// instance for testing
+    pub fn endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Assumes the IAM role `role_arn` with STS, using the credentials otherwise resolved from the
+    /// environment or profile, e.g. to query another account's catalog. The role's credentials
+    /// are refreshed automatically before they expire
+    pub fn assume_role(
+        mut self,
+        role_arn: impl Into<String>,
+        session_name: impl Into<String>,
+    ) -> Self {
+        self.assumed_role = Some(AssumedRole {
+            role_arn: role_arn.into(),
+            session_name: session_name.into(),
+        });
+        self
+    }
+
+    /// Enables caching of catalog reads, each entry living for `ttl`
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Enables caching of catalog reads, each entry living for [`DEFAULT_CACHE_TTL`]
+    pub fn cache(self) -> Self {
+        self.cache_ttl(DEFAULT_CACHE_TTL)
+    }
+
+    /// Overrides how throttled and transiently failing AWS calls are retried
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Overrides how long a single AWS request may take before it's abandoned
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Overrides how many AWS requests may be in flight at once
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Overrides the largest serialized response a listing tool returns. Larger results are cut
+    /// short and marked as truncated
+    pub fn max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Enables resolving database and table names by matching them ignoring case
+    pub fn case_insensitive_names(mut self, case_insensitive_names: bool) -> Self {
+        self.case_insensitive_names = case_insensitive_names;
+        self
+    }
+
+    /// Restricts the server to the databases matching any of the glob `patterns`, where `*`
+    /// matches any run of characters and `?` any single character. Databases outside the list are
+    /// left out of listings, and tools asked about them fail with an access denied error
+    pub fn allowed_databases(
+        mut self,
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_databases = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Hides the databases matching any of the glob `patterns` from the server, even if they're
+    /// also allowed by [`Self::allowed_databases`]
+    pub fn denied_databases(
+        mut self,
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.denied_databases = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Enables or disables the tools that change state in AWS, overriding
+    /// [`ALLOW_MUTATIONS_ENV`](crate::ALLOW_MUTATIONS_ENV)
+    pub fn allow_mutations(mut self, allow_mutations: bool) -> Self {
+        self.allow_mutations = Some(allow_mutations);
+        self
+    }
+
+    /// Overrides the instructions sent to clients on initialization, which default to
+    /// [`DEFAULT_INSTRUCTIONS`](crate::DEFAULT_INSTRUCTIONS)
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Loads the AWS configuration, builds the Glue and S3 clients and checks that the catalog is
+    /// reachable with the resolved credentials, failing rather than serving tools that can never
+    /// succeed. The settings are kept so that the clients can be rebuilt if AWS later rejects
+    /// their credentials
+    pub async fn build(self) -> anyhow::Result<GlueDataCatalog> {
+        let (client, s3_client) = self.clients().await;
+        client
+            .get_databases()
+            .send()
+            .await
+            .context("Couldn't connect to AWS Glue")?;
+
+        let reconnect = Arc::new(self.clone());
+        let mut catalog = self.build_with_client(client).with_s3_client(s3_client);
+        catalog.reconnect = Some(reconnect);
+        Ok(catalog)
+    }
+
+    /// Loads the AWS configuration and builds the Glue and S3 clients from it. The credentials are
+    /// cached by the clients and refreshed from the provider chain as they near expiry
+    pub(crate) async fn clients(&self) -> (aws_sdk_glue::Client, aws_sdk_s3::Client) {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = &self.region {
+            loader = loader.region(Region::new(region.clone()));
+        }
+        if let Some(profile_name) = &self.profile_name {
+            loader = loader.profile_name(profile_name);
+        }
+        if let Some(endpoint_url) = &self.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
+        let mut config = loader.load().await;
+        if let Some(AssumedRole {
+            role_arn,
+            session_name,
+        }) = &self.assumed_role
+        {
+            let provider = AssumeRoleProvider::builder(role_arn)
+                .session_name(session_name)
+                .configure(&config)
+                .build()
+                .await;
+            config = config
+                .to_builder()
+                .credentials_provider(SharedCredentialsProvider::new(provider))
+                .build();
+        }
+
+        // Retries are handled per tool by `send_with_retry`, so disable the SDK's own retries to
+        // avoid multiplying attempts
+        let glue_config = aws_sdk_glue::config::Builder::from(&config)
+            .retry_config(aws_config::retry::RetryConfig::disabled())
+            .build();
+        let s3_config = aws_sdk_s3::config::Builder::from(&config)
+            .retry_config(aws_config::retry::RetryConfig::disabled())
+            .build();
+        (
+            aws_sdk_glue::Client::from_conf(glue_config),
+            aws_sdk_s3::Client::from_conf(s3_config),
+        )
+    }
+
+    /// The ID of the AWS account owning the assumed role, whose catalog is queried by default
+    pub(crate) fn assumed_account_id(&self) -> Option<&str> {
+        let role_arn = &self.assumed_role.as_ref()?.role_arn;
+        role_arn
+            .split(':')
+            .nth(4)
+            .filter(|account| !account.is_empty())
+    }
+
+    /// Builds the catalog around an existing Glue client, ignoring the region, profile, endpoint
+    /// URL and assumed role. No S3 client is set, so tools that read table data are unavailable unless
+    /// one is added with [`GlueDataCatalog::with_s3_client`]
+    pub fn build_with_client(self, client: aws_sdk_glue::Client) -> GlueDataCatalog {
+        self.build_with_api(client)
+    }
+
+    /// Builds the catalog around any implementation of [`GlueApi`], such as a mock serving canned
+    /// responses, with the same caveats as [`Self::build_with_client`]
+    pub fn build_with_api(self, api: impl GlueApi) -> GlueDataCatalog {
+        let max_concurrent_requests = self
+            .max_concurrent_requests
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        GlueDataCatalog {
+            clients: Arc::new(RwLock::new(AwsClients {
+                glue: Arc::new(api),
+                s3: None,
+            })),
+            reconnect: None,
+            last_credential_refresh: Arc::new(Mutex::new(None)),
+            allow_mutations: self
+                .allow_mutations
+                .unwrap_or_else(GlueDataCatalog::mutations_allowed_from_env),
+            cache: self.cache_ttl.map(cache::new_cache),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            request_timeout: self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+            max_concurrent_requests,
+            request_permits: Arc::new(Semaphore::new(max_concurrent_requests)),
+            max_response_bytes: self
+                .max_response_bytes
+                .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES),
+            case_insensitive_names: self.case_insensitive_names,
+            database_access: DatabaseAccess::new(self.allowed_databases, self.denied_databases),
+            instructions: self.instructions,
+            auth_required: false,
+            confirmations: Confirmations::new(),
+            in_flight: TaskTracker::new(),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use serde_json::Value;
+use std::time::Duration;
+
+/// How long cached catalog reads are served before Glue is queried again, unless overridden
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Identifies a cached catalog read by its tool and request parameters. Every key carries the
+/// catalog ID so that reads against different accounts' catalogs never collide.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) enum CacheKey {
+    Databases {
+        catalog_id: Option<String>,
+        limit: Option<usize>,
+        next_token: Option<String>,
+    },
+    DatabaseMetadata {
+        catalog_id: Option<String>,
+        database_name: String,
+        name_prefix: Option<String>,
+        table_type: Option<String>,
+        limit: Option<usize>,
+        next_token: Option<String>,
+    },
+    TableMetadata {
+        catalog_id: Option<String>,
+        database_name: String,
+        table_name: String,
+    },
+}
+
+/// Serialized tool results keyed by the request that produced them
+pub(crate) type CatalogCache = moka::future::Cache<CacheKey, Value>;
+
+pub(crate) fn new_cache(ttl: Duration) -> CatalogCache {
+    moka::future::Cache::builder()
+        .time_to_live(ttl)
+        .support_invalidation_closures()
+        .build()
+}
+
+/// Drops the cached reads that a change to the table `table_name` in `database_name` makes stale:
+/// the table's metadata and every listing of its database's tables. Keys are matched in any
+/// catalog and ignoring case, since the default catalog may be cached under its ID as well as
+/// `None`, and a name may have been resolved ignoring case
+pub(crate) fn invalidate_table(cache: &CatalogCache, database_name: &str, table_name: &str) {
+    let (database, table) = (database_name.to_string(), table_name.to_string());
+    let stale = move |key: &CacheKey, _: &Value| match key {
+        CacheKey::DatabaseMetadata { database_name, .. } => {
+            database_name.eq_ignore_ascii_case(&database)
+        }
+        CacheKey::TableMetadata {
+            database_name,
+            table_name,
+            ..
+        } => {
+            database_name.eq_ignore_ascii_case(&database) && table_name.eq_ignore_ascii_case(&table)
+        }
+        CacheKey::Databases { .. } => false,
+    };
+    if let Err(e) = cache.invalidate_entries_if(stale) {
+        log::warn!(
+            "Clearing the whole cache, as stale entries couldn't be selected: {}",
+            e
+        );
+        cache.invalidate_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn table(database_name: &str, table_name: &str) -> CacheKey {
+        CacheKey::TableMetadata {
+            catalog_id: None,
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+        }
+    }
+
+    fn tables_in(database_name: &str) -> CacheKey {
+        CacheKey::DatabaseMetadata {
+            catalog_id: None,
+            database_name: database_name.into(),
+            name_prefix: None,
+            table_type: None,
+            limit: None,
+            next_token: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn invalidate_table_drops_only_the_reads_of_that_table_and_its_database() {
+        let cache = new_cache(DEFAULT_CACHE_TTL);
+        let databases = CacheKey::Databases {
+            catalog_id: None,
+            limit: None,
+            next_token: None,
+        };
+        for key in [
+            databases.clone(),
+            tables_in("sales"),
+            tables_in("marketing"),
+            table("sales", "orders"),
+            table("sales", "refunds"),
+            table("marketing", "orders"),
+        ] {
+            cache.insert(key, json!({})).await;
+        }
+
+        invalidate_table(&cache, "Sales", "ORDERS");
+        cache.run_pending_tasks().await;
+
+        assert!(cache.get(&table("sales", "orders")).await.is_none());
+        assert!(cache.get(&tables_in("sales")).await.is_none());
+        assert!(cache.get(&table("sales", "refunds")).await.is_some());
+        assert!(cache.get(&table("marketing", "orders")).await.is_some());
+        assert!(cache.get(&tables_in("marketing")).await.is_some());
+        assert!(cache.get(&databases).await.is_some());
+    }
+}
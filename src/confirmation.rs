@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+/// How long a confirmation token stays valid after it's issued
+pub(crate) const CONFIRMATION_TTL: Duration = Duration::from_secs(300);
+
+/// Tokens issued by destructive tools, each mapped to a description of the exact action it
+/// confirms. A destructive tool only proceeds when called again with a token issued for the same
+/// action, so that an agent can't delete anything in a single, possibly mistaken, call
+#[derive(Debug, Clone)]
+pub(crate) struct Confirmations {
+    ttl: Duration,
+    /// The action each token confirms and when it was issued
+    pending: moka::future::Cache<String, (String, Instant)>,
+}
+
+impl Confirmations {
+    pub(crate) fn new() -> Self {
+        Self::with_ttl(CONFIRMATION_TTL)
+    }
+
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            pending: moka::future::Cache::builder().time_to_live(ttl).build(),
+        }
+    }
+
+    /// Issues a single-use token confirming `action`. Tokens are drawn from the operating system's
+    /// secure random number generator, as guessing one would allow a destructive action
+    pub(crate) async fn issue(&self, action: &str) -> Result<String, getrandom::Error> {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes)?;
+        let token = bytes
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        self.pending
+            .insert(token.clone(), (action.into(), Instant::now()))
+            .await;
+        Ok(token)
+    }
+
+    /// Consumes `token`, returning whether it was issued for `action` and hasn't expired. A token
+    /// issued for a different action is consumed too, so it can't be retried against this one
+    pub(crate) async fn redeem(&self, token: &str, action: &str) -> bool {
+        // Checked here too, as the cache may not have evicted an expired token yet
+        self.pending
+            .remove(token)
+            .await
+            .is_some_and(|(confirmed, issued)| confirmed == action && issued.elapsed() < self.ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTION: &str = "Delete table 'sales.orders' from the default catalog";
+
+    #[tokio::test]
+    async fn tokens_confirm_the_action_they_were_issued_for_once() {
+        let confirmations = Confirmations::new();
+        let token = confirmations.issue(ACTION).await.unwrap();
+
+        assert!(confirmations.redeem(&token, ACTION).await);
+        assert!(!confirmations.redeem(&token, ACTION).await);
+    }
+
+    #[tokio::test]
+    async fn tokens_for_another_action_are_rejected_and_consumed() {
+        let confirmations = Confirmations::new();
+        let token = confirmations.issue(ACTION).await.unwrap();
+
+        assert!(
+            !confirmations
+                .redeem(
+                    &token,
+                    "Delete table 'sales.refunds' from the default catalog"
+                )
+                .await
+        );
+        assert!(!confirmations.redeem(&token, ACTION).await);
+    }
+
+    #[tokio::test]
+    async fn unknown_tokens_are_rejected() {
+        let confirmations = Confirmations::new();
+        confirmations.issue(ACTION).await.unwrap();
+
+        assert!(!confirmations.redeem("not-a-token", ACTION).await);
+    }
+
+    #[tokio::test]
+    async fn tokens_expire_after_the_ttl() {
+        let confirmations = Confirmations::with_ttl(Duration::from_millis(20));
+        let token = confirmations.issue(ACTION).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert!(!confirmations.redeem(&token, ACTION).await);
+    }
+
+    #[tokio::test]
+    async fn tokens_are_unique_128_bit_hex_strings() {
+        let confirmations = Confirmations::new();
+        let first = confirmations.issue(ACTION).await.unwrap();
+        let second = confirmations.issue(ACTION).await.unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 32);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
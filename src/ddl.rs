@@ -0,0 +1,368 @@
+use aws_sdk_glue::types::{Column, Table};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Hive/Athena DDL keywords that must be quoted when used as identifiers
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "ALTER",
+    "AND",
+    "ARRAY",
+    "AS",
+    "AUTHORIZATION",
+    "BETWEEN",
+    "BIGINT",
+    "BINARY",
+    "BOOLEAN",
+    "BOTH",
+    "BY",
+    "CASE",
+    "CAST",
+    "CHAR",
+    "COLUMN",
+    "CONF",
+    "CONSTRAINT",
+    "COMMIT",
+    "CREATE",
+    "CROSS",
+    "CUBE",
+    "CURRENT",
+    "CURRENT_DATE",
+    "CURRENT_TIMESTAMP",
+    "CURSOR",
+    "DATABASE",
+    "DATE",
+    "DAYOFWEEK",
+    "DECIMAL",
+    "DELETE",
+    "DESCRIBE",
+    "DISTINCT",
+    "DIV",
+    "DOUBLE",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCHANGE",
+    "EXISTS",
+    "EXTENDED",
+    "EXTERNAL",
+    "EXTRACT",
+    "FALSE",
+    "FETCH",
+    "FLOAT",
+    "FLOOR",
+    "FOLLOWING",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "FUNCTION",
+    "GRANT",
+    "GROUP",
+    "GROUPING",
+    "HAVING",
+    "IF",
+    "IMPORT",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INT",
+    "INTEGER",
+    "INTERSECT",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "JOIN",
+    "LATERAL",
+    "LEFT",
+    "LESS",
+    "LIKE",
+    "LOCAL",
+    "MACRO",
+    "MAP",
+    "MORE",
+    "NONE",
+    "NOT",
+    "NULL",
+    "NUMERIC",
+    "OF",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUT",
+    "OUTER",
+    "OVER",
+    "PARTIALSCAN",
+    "PARTITION",
+    "PERCENT",
+    "PRECEDING",
+    "PRECISION",
+    "PRESERVE",
+    "PRIMARY",
+    "PROCEDURE",
+    "RANGE",
+    "READS",
+    "REDUCE",
+    "REGEXP",
+    "REFERENCES",
+    "REVOKE",
+    "RIGHT",
+    "RLIKE",
+    "ROLLBACK",
+    "ROLLUP",
+    "ROW",
+    "ROWS",
+    "SELECT",
+    "SET",
+    "SMALLINT",
+    "START",
+    "TABLE",
+    "TABLESAMPLE",
+    "THEN",
+    "TIME",
+    "TIMESTAMP",
+    "TO",
+    "TRANSFORM",
+    "TRIGGER",
+    "TRUE",
+    "TRUNCATE",
+    "UNBOUNDED",
+    "UNION",
+    "UNIQUEJOIN",
+    "UPDATE",
+    "USER",
+    "USING",
+    "UTC_TIMESTAMP",
+    "VALUES",
+    "VARCHAR",
+    "VIEWS",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// Builds a Hive/Athena `CREATE TABLE` statement from a Glue table definition
+pub(crate) fn create_table_ddl(database_name: &str, table: &Table) -> String {
+    let storage_descriptor = table.storage_descriptor();
+    let external = table.table_type() != Some("MANAGED_TABLE");
+
+    let mut ddl = format!(
+        "CREATE {}TABLE {}.{} (\n",
+        if external { "EXTERNAL " } else { "" },
+        quote_identifier(database_name),
+        quote_identifier(table.name())
+    );
+    ddl.push_str(&column_list(
+        storage_descriptor
+            .map(|sd| sd.columns())
+            .unwrap_or_default(),
+    ));
+    ddl.push_str("\n)");
+
+    if let Some(description) = table.description() {
+        let _ = write!(ddl, "\nCOMMENT {}", quote_literal(description));
+    }
+
+    if !table.partition_keys().is_empty() {
+        let _ = write!(
+            ddl,
+            "\nPARTITIONED BY (\n{}\n)",
+            column_list(table.partition_keys())
+        );
+    }
+
+    if let Some(sd) = storage_descriptor {
+        if let Some(serde_info) = sd.serde_info() {
+            if let Some(library) = serde_info.serialization_library() {
+                let _ = write!(ddl, "\nROW FORMAT SERDE {}", quote_literal(library));
+            }
+            if let Some(properties) = serde_info.parameters().filter(|p| !p.is_empty()) {
+                let _ = write!(
+                    ddl,
+                    "\nWITH SERDEPROPERTIES (\n{}\n)",
+                    property_list(properties)
+                );
+            }
+        }
+        if let Some(input_format) = sd.input_format() {
+            let _ = write!(
+                ddl,
+                "\nSTORED AS INPUTFORMAT {}",
+                quote_literal(input_format)
+            );
+        }
+        if let Some(output_format) = sd.output_format() {
+            let _ = write!(ddl, "\nOUTPUTFORMAT {}", quote_literal(output_format));
+        }
+        if let Some(location) = sd.location() {
+            let _ = write!(ddl, "\nLOCATION {}", quote_literal(location));
+        }
+    }
+
+    if let Some(parameters) = table.parameters().filter(|p| !p.is_empty()) {
+        let _ = write!(ddl, "\nTBLPROPERTIES (\n{}\n)", property_list(parameters));
+    }
+
+    ddl
+}
+
+fn column_list(columns: &[Column]) -> String {
+    columns
+        .iter()
+        .map(|col| {
+            let mut definition = format!(
+                "  {} {}",
+                quote_identifier(col.name()),
+                col.r#type().unwrap_or("string")
+            );
+            if let Some(comment) = col.comment() {
+                let _ = write!(definition, " COMMENT {}", quote_literal(comment));
+            }
+            definition
+        })
+        .collect::<Vec<String>>()
+        .join(",\n")
+}
+
+/// Renders properties sorted by key so the generated DDL is stable between calls
+fn property_list(properties: &HashMap<String, String>) -> String {
+    properties
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(key, value)| format!("  {}={}", quote_literal(key), quote_literal(value)))
+        .collect::<Vec<String>>()
+        .join(",\n")
+}
+
+/// Backtick-quotes identifiers that are reserved words or contain characters outside
+/// `[A-Za-z0-9_]`
+fn quote_identifier(name: &str) -> String {
+    let is_plain = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let is_reserved = RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(name));
+    if is_plain && !is_reserved {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_glue::types::{SerDeInfo, StorageDescriptor};
+
+    fn column(name: &str, data_type: &str) -> Column {
+        Column::builder()
+            .name(name)
+            .r#type(data_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn create_table_ddl_renders_an_external_table() {
+        let table = Table::builder()
+            .name("orders")
+            .description("Customer's orders")
+            .table_type("EXTERNAL_TABLE")
+            .storage_descriptor(
+                StorageDescriptor::builder()
+                    .columns(column("id", "bigint"))
+                    .columns(
+                        Column::builder()
+                            .name("date")
+                            .r#type("date")
+                            .comment("Order date")
+                            .build()
+                            .unwrap(),
+                    )
+                    .serde_info(
+                        SerDeInfo::builder()
+                            .serialization_library(
+                                "org.apache.hadoop.hive.ql.io.parquet.serde.ParquetHiveSerDe",
+                            )
+                            .parameters("serialization.format", "1")
+                            .build(),
+                    )
+                    .input_format("org.apache.hadoop.hive.ql.io.parquet.MapredParquetInputFormat")
+                    .output_format("org.apache.hadoop.hive.ql.io.parquet.MapredParquetOutputFormat")
+                    .location("s3://warehouse/sales/orders/")
+                    .build(),
+            )
+            .partition_keys(column("dt", "string"))
+            .parameters("classification", "parquet")
+            .parameters("EXTERNAL", "TRUE")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            create_table_ddl("sales", &table),
+            "CREATE EXTERNAL TABLE sales.orders (
+  id bigint,
+  `date` date COMMENT 'Order date'
+)
+COMMENT 'Customer\\'s orders'
+PARTITIONED BY (
+  dt string
+)
+ROW FORMAT SERDE 'org.apache.hadoop.hive.ql.io.parquet.serde.ParquetHiveSerDe'
+WITH SERDEPROPERTIES (
+  'serialization.format'='1'
+)
+STORED AS INPUTFORMAT 'org.apache.hadoop.hive.ql.io.parquet.MapredParquetInputFormat'
+OUTPUTFORMAT 'org.apache.hadoop.hive.ql.io.parquet.MapredParquetOutputFormat'
+LOCATION 's3://warehouse/sales/orders/'
+TBLPROPERTIES (
+  'EXTERNAL'='TRUE',
+  'classification'='parquet'
+)"
+        );
+    }
+
+    #[test]
+    fn create_table_ddl_renders_a_managed_table_without_storage() {
+        let table = Table::builder()
+            .name("scratch")
+            .table_type("MANAGED_TABLE")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            create_table_ddl("tmp", &table),
+            "CREATE TABLE tmp.scratch (\n\n)"
+        );
+    }
+
+    #[test]
+    fn columns_without_a_type_default_to_string() {
+        let columns = [Column::builder().name("payload").build().unwrap()];
+
+        assert_eq!(column_list(&columns), "  payload string");
+    }
+
+    #[test]
+    fn quote_identifier_quotes_reserved_words_and_unusual_characters() {
+        assert_eq!(quote_identifier("orders"), "orders");
+        assert_eq!(quote_identifier("order_id"), "order_id");
+        assert_eq!(quote_identifier("date"), "`date`");
+        assert_eq!(quote_identifier("Table"), "`Table`");
+        assert_eq!(quote_identifier("order-id"), "`order-id`");
+        assert_eq!(quote_identifier("odd`name"), "`odd``name`");
+    }
+
+    #[test]
+    fn quote_literal_escapes_quotes_and_backslashes() {
+        assert_eq!(quote_literal("plain"), "'plain'");
+        assert_eq!(quote_literal("it's"), "'it\\'s'");
+        assert_eq!(quote_literal("C:\\path"), "'C:\\\\path'");
+    }
+}
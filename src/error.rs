@@ -0,0 +1,267 @@
+use aws_sdk_glue::config::http::HttpResponse;
+use aws_sdk_glue::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use metrics::counter;
+use rmcp::Error as McpError;
+use rmcp::model::ErrorCode;
+use serde_json::{Value, json};
+use std::fmt;
+
+/// Returned when AWS throttled a request, even after retrying
+pub const THROTTLED: ErrorCode = ErrorCode(-32010);
+/// Returned when the server's AWS credentials, or its database allow and deny lists, don't
+/// permit a request
+pub const ACCESS_DENIED: ErrorCode = ErrorCode(-32011);
+/// Returned when AWS fails a request for any other reason
+pub const AWS_ERROR: ErrorCode = ErrorCode(-32012);
+/// Returned when AWS didn't answer a request in time, even after retrying
+pub const TIMEOUT: ErrorCode = ErrorCode(-32013);
+
+const NOT_FOUND_CODES: &[&str] = &["EntityNotFoundException"];
+const THROTTLED_CODES: &[&str] = &[
+    "ThrottlingException",
+    "Throttling",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+];
+/// Codes AWS returns when the credentials themselves are stale or unknown, rather than lacking
+/// permission, which reloading them may fix
+const CREDENTIALS_CODES: &[&str] = &[
+    "ExpiredTokenException",
+    "ExpiredToken",
+    "UnrecognizedClientException",
+    "InvalidClientTokenId",
+];
+const ACCESS_DENIED_CODES: &[&str] = &[
+    "AccessDeniedException",
+    "UnrecognizedClientException",
+    "ExpiredTokenException",
+    "InvalidClientTokenId",
+];
+
+/// Whether an AWS SDK error reports that the requested entity doesn't exist
+pub(crate) fn is_not_found<E: ProvideErrorMetadata>(error: &SdkError<E, HttpResponse>) -> bool {
+    error
+        .code()
+        .is_some_and(|code| NOT_FOUND_CODES.contains(&code))
+}
+
+/// Whether an AWS SDK error reports that the request's credentials have expired or aren't
+/// recognised
+pub(crate) fn is_credentials_error<E: ProvideErrorMetadata>(
+    error: &SdkError<E, HttpResponse>,
+) -> bool {
+    error
+        .code()
+        .is_some_and(|code| CREDENTIALS_CODES.contains(&code))
+}
+
+/// The categories of failure a tool can report, each converting into an [`McpError`] with a
+/// distinct code so that clients can tell them apart
+#[derive(Debug)]
+pub enum GlueMcpError {
+    /// The requested database, table, crawler, job or other entity doesn't exist
+    NotFound {
+        message: String,
+        data: Option<Value>,
+    },
+    /// AWS throttled the request
+    Throttled { message: String },
+    /// AWS didn't answer the request before the request timeout elapsed
+    Timeout { message: String },
+    /// The server's AWS credentials, or its own database allow and deny lists, don't permit the
+    /// request
+    AccessDenied { message: String },
+    /// The tool was called with invalid parameters
+    InvalidParams {
+        message: String,
+        data: Option<Value>,
+    },
+    /// The request can't be carried out in the server's or the resource's current state
+    InvalidRequest {
+        message: String,
+        data: Option<Value>,
+    },
+    /// The tool's result couldn't be serialized
+    Serialization { message: String },
+    /// AWS failed the request for any other reason
+    Aws {
+        message: String,
+        aws_error_code: Option<String>,
+    },
+}
+
+impl GlueMcpError {
+    /// Classifies an AWS SDK error by its error code, prefixing its message with `context`
+    pub fn from_aws<E>(context: &str, error: &SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+    {
+        let message = format!("{context}: {}", DisplayErrorContext(error));
+        if let SdkError::TimeoutError(_) = error {
+            return Self::Timeout { message };
+        }
+        match error.code() {
+            Some(code) if NOT_FOUND_CODES.contains(&code) => Self::NotFound {
+                message,
+                data: None,
+            },
+            Some(code) if THROTTLED_CODES.contains(&code) => Self::Throttled { message },
+            Some(code) if ACCESS_DENIED_CODES.contains(&code) => Self::AccessDenied { message },
+            code => Self::Aws {
+                message,
+                aws_error_code: code.map(Into::into),
+            },
+        }
+    }
+
+    /// Like [`Self::from_aws`], but reports a missing entity as "`{entity}` does not exist", with
+    /// `data` identifying it, rather than with the raw AWS message
+    pub fn from_aws_or_not_found<E>(
+        context: &str,
+        entity: &str,
+        data: Value,
+        error: &SdkError<E, HttpResponse>,
+    ) -> Self
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+    {
+        if is_not_found(error) {
+            return Self::not_found(format!("{entity} does not exist"), data);
+        }
+        Self::from_aws(context, error)
+    }
+
+    pub fn not_found(message: impl Into<String>, data: Value) -> Self {
+        Self::NotFound {
+            message: message.into(),
+            data: Some(data),
+        }
+    }
+
+    pub fn access_denied(message: impl Into<String>) -> Self {
+        Self::AccessDenied {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>, data: Value) -> Self {
+        Self::InvalidParams {
+            message: message.into(),
+            data: Some(data),
+        }
+    }
+
+    pub fn invalid_request(message: impl Into<String>, data: Option<Value>) -> Self {
+        Self::InvalidRequest {
+            message: message.into(),
+            data,
+        }
+    }
+
+    pub fn serialization(error: serde_json::Error) -> Self {
+        Self::Serialization {
+            message: format!("Failed to serialize result: {error}"),
+        }
+    }
+
+    /// A short, stable name for the category, used in metric names and error data
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => "not_found",
+            Self::Throttled { .. } => "throttled",
+            Self::Timeout { .. } => "timeout",
+            Self::AccessDenied { .. } => "access_denied",
+            Self::InvalidParams { .. } => "invalid_params",
+            Self::InvalidRequest { .. } => "invalid_request",
+            Self::Serialization { .. } => "serde_error",
+            Self::Aws { .. } => "aws_call_error",
+        }
+    }
+
+    /// Counts the error against `tool` as `errors.<tool>.<kind>` and converts it for the client
+    pub fn into_mcp(self, tool: &str) -> McpError {
+        counter!(format!("errors.{tool}.{}", self.kind())).increment(1);
+        self.into()
+    }
+}
+
+impl fmt::Display for GlueMcpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { message, .. }
+            | Self::Throttled { message }
+            | Self::Timeout { message }
+            | Self::AccessDenied { message }
+            | Self::InvalidParams { message, .. }
+            | Self::InvalidRequest { message, .. }
+            | Self::Serialization { message }
+            | Self::Aws { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for GlueMcpError {}
+
+/// Merges the error kind into the structured data sent to the client
+fn with_kind(kind: &str, data: Option<Value>) -> Value {
+    let mut data = match data {
+        Some(Value::Object(map)) => map,
+        Some(other) => [("details".to_string(), other)].into_iter().collect(),
+        None => Default::default(),
+    };
+    data.insert("kind".into(), kind.into());
+    Value::Object(data)
+}
+
+impl From<GlueMcpError> for McpError {
+    fn from(error: GlueMcpError) -> Self {
+        let kind = error.kind();
+        match error {
+            GlueMcpError::NotFound { message, data } => {
+                McpError::resource_not_found(message, Some(with_kind(kind, data)))
+            }
+            GlueMcpError::Throttled { message } => {
+                McpError::new(THROTTLED, message, Some(with_kind(kind, None)))
+            }
+            GlueMcpError::Timeout { message } => {
+                McpError::new(TIMEOUT, message, Some(with_kind(kind, None)))
+            }
+            GlueMcpError::AccessDenied { message } => {
+                McpError::new(ACCESS_DENIED, message, Some(with_kind(kind, None)))
+            }
+            GlueMcpError::InvalidParams { message, data } => {
+                McpError::invalid_params(message, Some(with_kind(kind, data)))
+            }
+            GlueMcpError::InvalidRequest { message, data } => {
+                McpError::invalid_request(message, Some(with_kind(kind, data)))
+            }
+            GlueMcpError::Serialization { message } => {
+                McpError::internal_error(message, Some(with_kind(kind, None)))
+            }
+            GlueMcpError::Aws {
+                message,
+                aws_error_code,
+            } => McpError::new(
+                AWS_ERROR,
+                message,
+                Some(with_kind(
+                    kind,
+                    Some(json!({"aws_error_code": aws_error_code})),
+                )),
+            ),
+        }
+    }
+}
+
+/// Adds the ID of the tool call that failed to the error's data, so that users can find the
+/// call's logs
+pub(crate) fn with_request_id(mut error: McpError, request_id: &str) -> McpError {
+    let mut data = match error.data.take() {
+        Some(Value::Object(map)) => map,
+        Some(other) => [("details".to_string(), other)].into_iter().collect(),
+        None => Default::default(),
+    };
+    data.insert("request_id".into(), request_id.into());
+    error.data = Some(Value::Object(data));
+    error
+}
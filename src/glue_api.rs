@@ -0,0 +1,336 @@
+use aws_sdk_glue::config::http::HttpResponse;
+use aws_sdk_glue::error::SdkError;
+use aws_sdk_glue::operation;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The eventual outcome of a Glue operation
+pub type GlueFuture<O, E> =
+    Pin<Box<dyn Future<Output = Result<O, SdkError<E, HttpResponse>>> + Send>>;
+
+/// Declares [`GlueApi`] with a method per operation, each taking the operation's input builder,
+/// and implements it for the SDK client by sending the input with it
+macro_rules! glue_api {
+    ($($operation:ident($input:ident) -> $output:ident, $error:ident;)*) => {
+        /// The AWS Glue operations the tools call. It's implemented for [`aws_sdk_glue::Client`],
+        /// and other implementations let the tools run against canned responses, e.g. in unit
+        /// tests, through [`GlueDataCatalogBuilder::build_with_api`](crate::GlueDataCatalogBuilder::build_with_api).
+        /// Every operation fails with a construction error unless implemented, so a mock only
+        /// needs the operations its tests call
+        pub trait GlueApi: fmt::Debug + Send + Sync + 'static {
+            /// The region requests are sent to, if known
+            fn region(&self) -> Option<String> {
+                None
+            }
+
+            $(
+                #[doc = concat!("Sends a `", stringify!($operation), "` request")]
+                fn $operation(
+                    &self,
+                    _input: operation::$operation::builders::$input,
+                ) -> GlueFuture<operation::$operation::$output, operation::$operation::$error> {
+                    not_implemented(stringify!($operation))
+                }
+            )*
+        }
+
+        impl GlueApi for aws_sdk_glue::Client {
+            fn region(&self) -> Option<String> {
+                self.config().region().map(ToString::to_string)
+            }
+
+            $(
+                fn $operation(
+                    &self,
+                    input: operation::$operation::builders::$input,
+                ) -> GlueFuture<operation::$operation::$output, operation::$operation::$error> {
+                    let client = self.clone();
+                    Box::pin(async move { input.send_with(&client).await })
+                }
+            )*
+        }
+    };
+}
+
+glue_api! {
+    batch_stop_job_run(BatchStopJobRunInputBuilder) -> BatchStopJobRunOutput, BatchStopJobRunError;
+    check_schema_version_validity(CheckSchemaVersionValidityInputBuilder)
+        -> CheckSchemaVersionValidityOutput, CheckSchemaVersionValidityError;
+    delete_table(DeleteTableInputBuilder) -> DeleteTableOutput, DeleteTableError;
+    get_classifiers(GetClassifiersInputBuilder) -> GetClassifiersOutput, GetClassifiersError;
+    get_column_statistics_for_table(GetColumnStatisticsForTableInputBuilder)
+        -> GetColumnStatisticsForTableOutput, GetColumnStatisticsForTableError;
+    get_connections(GetConnectionsInputBuilder) -> GetConnectionsOutput, GetConnectionsError;
+    get_crawler(GetCrawlerInputBuilder) -> GetCrawlerOutput, GetCrawlerError;
+    get_crawler_metrics(GetCrawlerMetricsInputBuilder)
+        -> GetCrawlerMetricsOutput, GetCrawlerMetricsError;
+    get_crawlers(GetCrawlersInputBuilder) -> GetCrawlersOutput, GetCrawlersError;
+    get_data_catalog_encryption_settings(GetDataCatalogEncryptionSettingsInputBuilder)
+        -> GetDataCatalogEncryptionSettingsOutput, GetDataCatalogEncryptionSettingsError;
+    get_data_quality_result(GetDataQualityResultInputBuilder)
+        -> GetDataQualityResultOutput, GetDataQualityResultError;
+    get_database(GetDatabaseInputBuilder) -> GetDatabaseOutput, GetDatabaseError;
+    get_databases(GetDatabasesInputBuilder) -> GetDatabasesOutput, GetDatabasesError;
+    get_job(GetJobInputBuilder) -> GetJobOutput, GetJobError;
+    get_job_run(GetJobRunInputBuilder) -> GetJobRunOutput, GetJobRunError;
+    get_job_runs(GetJobRunsInputBuilder) -> GetJobRunsOutput, GetJobRunsError;
+    get_jobs(GetJobsInputBuilder) -> GetJobsOutput, GetJobsError;
+    get_ml_transforms(GetMlTransformsInputBuilder) -> GetMlTransformsOutput, GetMlTransformsError;
+    get_partition_indexes(GetPartitionIndexesInputBuilder)
+        -> GetPartitionIndexesOutput, GetPartitionIndexesError;
+    get_partition(GetPartitionInputBuilder) -> GetPartitionOutput, GetPartitionError;
+    get_partitions(GetPartitionsInputBuilder) -> GetPartitionsOutput, GetPartitionsError;
+    get_resource_policy(GetResourcePolicyInputBuilder)
+        -> GetResourcePolicyOutput, GetResourcePolicyError;
+    get_schema(GetSchemaInputBuilder) -> GetSchemaOutput, GetSchemaError;
+    get_schema_by_definition(GetSchemaByDefinitionInputBuilder)
+        -> GetSchemaByDefinitionOutput, GetSchemaByDefinitionError;
+    get_schema_version(GetSchemaVersionInputBuilder)
+        -> GetSchemaVersionOutput, GetSchemaVersionError;
+    get_table(GetTableInputBuilder) -> GetTableOutput, GetTableError;
+    get_table_optimizer(GetTableOptimizerInputBuilder)
+        -> GetTableOptimizerOutput, GetTableOptimizerError;
+    get_table_versions(GetTableVersionsInputBuilder)
+        -> GetTableVersionsOutput, GetTableVersionsError;
+    get_tables(GetTablesInputBuilder) -> GetTablesOutput, GetTablesError;
+    get_tags(GetTagsInputBuilder) -> GetTagsOutput, GetTagsError;
+    get_triggers(GetTriggersInputBuilder) -> GetTriggersOutput, GetTriggersError;
+    get_user_defined_functions(GetUserDefinedFunctionsInputBuilder)
+        -> GetUserDefinedFunctionsOutput, GetUserDefinedFunctionsError;
+    get_workflow(GetWorkflowInputBuilder) -> GetWorkflowOutput, GetWorkflowError;
+    list_data_quality_rulesets(ListDataQualityRulesetsInputBuilder)
+        -> ListDataQualityRulesetsOutput, ListDataQualityRulesetsError;
+    list_registries(ListRegistriesInputBuilder) -> ListRegistriesOutput, ListRegistriesError;
+    list_sessions(ListSessionsInputBuilder) -> ListSessionsOutput, ListSessionsError;
+    search_tables(SearchTablesInputBuilder) -> SearchTablesOutput, SearchTablesError;
+    start_crawler(StartCrawlerInputBuilder) -> StartCrawlerOutput, StartCrawlerError;
+    start_job_run(StartJobRunInputBuilder) -> StartJobRunOutput, StartJobRunError;
+    stop_crawler(StopCrawlerInputBuilder) -> StopCrawlerOutput, StopCrawlerError;
+    test_connection(TestConnectionInputBuilder) -> TestConnectionOutput, TestConnectionError;
+}
+
+/// Fails an operation a [`GlueApi`] implementation doesn't provide
+fn not_implemented<O, E>(operation: &str) -> GlueFuture<O, E>
+where
+    O: Send + 'static,
+    E: Send + 'static,
+{
+    let error =
+        SdkError::construction_failure(format!("{operation} isn't implemented by this GlueApi"));
+    Box::pin(std::future::ready(Err(error)))
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::{GlueApi, GlueFuture};
+    use aws_sdk_glue::config::http::HttpResponse;
+    use aws_sdk_glue::error::{ErrorMetadata, SdkError};
+    use aws_sdk_glue::operation::get_column_statistics_for_table::builders::GetColumnStatisticsForTableInputBuilder;
+    use aws_sdk_glue::operation::get_column_statistics_for_table::{
+        GetColumnStatisticsForTableError, GetColumnStatisticsForTableOutput,
+    };
+    use aws_sdk_glue::operation::get_databases::builders::GetDatabasesInputBuilder;
+    use aws_sdk_glue::operation::get_databases::{GetDatabasesError, GetDatabasesOutput};
+    use aws_sdk_glue::operation::get_table::builders::GetTableInputBuilder;
+    use aws_sdk_glue::operation::get_table::{GetTableError, GetTableOutput};
+    use aws_sdk_glue::operation::start_job_run::builders::StartJobRunInputBuilder;
+    use aws_sdk_glue::operation::start_job_run::{StartJobRunError, StartJobRunOutput};
+    use aws_sdk_glue::types::{
+        Column, ColumnError, Database, ErrorDetail, StorageDescriptor, Table,
+    };
+    use aws_smithy_types::body::SdkBody;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    /// A [`GlueApi`] serving canned databases and tables, which records the operations it's sent
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct MockGlue {
+        /// The database names on each page `get_databases` returns. Every page but the last
+        /// carries the token `page-<index of the next page>`
+        pub(crate) database_pages: Vec<Vec<String>>,
+        /// The tables `get_table` finds, with the name of the database each is in
+        pub(crate) tables: Vec<(String, Table)>,
+        /// The error every `start_job_run` fails with, or `None` to start a run
+        pub(crate) start_job_run_error: Option<fn() -> SdkError<StartJobRunError, HttpResponse>>,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl MockGlue {
+        pub(crate) fn with_database_pages(pages: &[&[&str]]) -> Self {
+            Self {
+                database_pages: pages
+                    .iter()
+                    .map(|page| page.iter().map(ToString::to_string).collect())
+                    .collect(),
+                ..Self::default()
+            }
+        }
+
+        /// Adds a table with the given columns, named and typed as `(name, type)`
+        pub(crate) fn with_table(
+            mut self,
+            database_name: &str,
+            table_name: &str,
+            columns: &[(&str, &str)],
+        ) -> Self {
+            let columns = columns
+                .iter()
+                .map(|(name, data_type)| {
+                    Column::builder()
+                        .name(*name)
+                        .r#type(*data_type)
+                        .build()
+                        .unwrap()
+                })
+                .collect();
+            let table = Table::builder()
+                .name(table_name)
+                .storage_descriptor(
+                    StorageDescriptor::builder()
+                        .set_columns(Some(columns))
+                        .location(format!("s3://warehouse/{database_name}/{table_name}/"))
+                        .build(),
+                )
+                .build()
+                .unwrap();
+            self.tables.push((database_name.into(), table));
+            self
+        }
+
+        /// How many times `operation` has been sent
+        pub(crate) fn calls(&self, operation: &str) -> usize {
+            self.calls
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .filter(|call| **call == operation)
+                .count()
+        }
+
+        fn record(&self, operation: &'static str) {
+            self.calls
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(operation);
+        }
+    }
+
+    /// An error response from Glue carrying `error`, with the given HTTP status
+    pub(crate) fn service_error<E>(error: E, status: u16) -> SdkError<E, HttpResponse> {
+        SdkError::service_error(
+            error,
+            HttpResponse::new(status.try_into().unwrap(), SdkBody::empty()),
+        )
+    }
+
+    /// The error metadata Glue returns with the given error code
+    pub(crate) fn error_code(code: &str) -> ErrorMetadata {
+        ErrorMetadata::builder().code(code).build()
+    }
+
+    fn ready<O, E>(result: Result<O, SdkError<E, HttpResponse>>) -> GlueFuture<O, E>
+    where
+        O: Send + 'static,
+        E: Send + 'static,
+    {
+        Box::pin(std::future::ready(result))
+    }
+
+    impl GlueApi for MockGlue {
+        fn region(&self) -> Option<String> {
+            Some("eu-west-1".into())
+        }
+
+        fn get_databases(
+            &self,
+            input: GetDatabasesInputBuilder,
+        ) -> GlueFuture<GetDatabasesOutput, GetDatabasesError> {
+            self.record("get_databases");
+            let page = input.get_next_token().as_deref().map_or(0, |token| {
+                token.trim_start_matches("page-").parse::<usize>().unwrap()
+            });
+            let databases = self
+                .database_pages
+                .get(page)
+                .into_iter()
+                .flatten()
+                .map(|name| Database::builder().name(name).build().unwrap())
+                .collect();
+            let next_token =
+                (page + 1 < self.database_pages.len()).then(|| format!("page-{}", page + 1));
+            ready(Ok(GetDatabasesOutput::builder()
+                .set_database_list(Some(databases))
+                .set_next_token(next_token)
+                .build()
+                .unwrap()))
+        }
+
+        fn get_table(
+            &self,
+            input: GetTableInputBuilder,
+        ) -> GlueFuture<GetTableOutput, GetTableError> {
+            self.record("get_table");
+            let table = self.tables.iter().find(|(database_name, table)| {
+                input.get_database_name().as_ref() == Some(database_name)
+                    && input.get_name().as_deref() == Some(table.name())
+            });
+            ready(match table {
+                Some((_, table)) => Ok(GetTableOutput::builder().table(table.clone()).build()),
+                None => Err(service_error(
+                    GetTableError::generic(error_code("EntityNotFoundException")),
+                    400,
+                )),
+            })
+        }
+
+        /// Computes no statistics, failing for every requested column the table doesn't have
+        fn get_column_statistics_for_table(
+            &self,
+            input: GetColumnStatisticsForTableInputBuilder,
+        ) -> GlueFuture<GetColumnStatisticsForTableOutput, GetColumnStatisticsForTableError>
+        {
+            self.record("get_column_statistics_for_table");
+            let columns = self
+                .tables
+                .iter()
+                .find(|(database_name, table)| {
+                    input.get_database_name().as_ref() == Some(database_name)
+                        && input.get_table_name().as_deref() == Some(table.name())
+                })
+                .and_then(|(_, table)| table.storage_descriptor())
+                .map(|sd| sd.columns())
+                .unwrap_or_default();
+            let errors = input
+                .get_column_names()
+                .iter()
+                .flatten()
+                .filter(|name| !columns.iter().any(|column| column.name() == name.as_str()))
+                .map(|name| {
+                    ColumnError::builder()
+                        .column_name(name)
+                        .error(
+                            ErrorDetail::builder()
+                                .error_code("EntityNotFoundException")
+                                .error_message(format!("Column {name} not found"))
+                                .build(),
+                        )
+                        .build()
+                })
+                .collect();
+            ready(Ok(GetColumnStatisticsForTableOutput::builder()
+                .set_errors(Some(errors))
+                .build()))
+        }
+
+        fn start_job_run(
+            &self,
+            _input: StartJobRunInputBuilder,
+        ) -> GlueFuture<StartJobRunOutput, StartJobRunError> {
+            self.record("start_job_run");
+            ready(match self.start_job_run_error {
+                Some(error) => Err(error()),
+                None => Ok(StartJobRunOutput::builder().job_run_id("jr_1").build()),
+            })
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use aws_sdk_glue::types::Column;
+use serde_json::{Map, Value, json};
+
+/// Builds a JSON Schema document describing a record of the given table's columns
+pub(crate) fn table_json_schema(table_name: &str, columns: &[Column]) -> Value {
+    let properties = columns
+        .iter()
+        .map(|col| {
+            let mut schema = col
+                .r#type()
+                .map_or_else(|| json!({"type": "string"}), glue_type_to_json_schema);
+            if let (Some(comment), Value::Object(schema)) = (col.comment(), &mut schema) {
+                schema.insert("description".into(), comment.into());
+            }
+            (col.name().to_string(), schema)
+        })
+        .collect::<Map<String, Value>>();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": table_name,
+        "type": "object",
+        "properties": properties,
+    })
+}
+
+/// Maps a Glue (Hive) type string such as `array<struct<id:bigint,tags:map<string,string>>>` to
+/// a JSON Schema, recursing into nested types. Unrecognised types map to a plain string schema.
+pub(crate) fn glue_type_to_json_schema(glue_type: &str) -> Value {
+    let glue_type = glue_type.trim();
+
+    if let Some(element) = type_arguments(glue_type, "array") {
+        return json!({"type": "array", "items": glue_type_to_json_schema(element)});
+    }
+
+    if let Some(arguments) = type_arguments(glue_type, "map") {
+        return match split_top_level(arguments, ',').as_slice() {
+            [_key, value] => json!({
+                "type": "object",
+                "additionalProperties": glue_type_to_json_schema(value),
+            }),
+            _ => json!({"type": "object"}),
+        };
+    }
+
+    if let Some(fields) = type_arguments(glue_type, "struct") {
+        let properties = split_top_level(fields, ',')
+            .into_iter()
+            .filter_map(|field| field.split_once(':'))
+            .map(|(name, field_type)| {
+                (
+                    name.trim().to_string(),
+                    glue_type_to_json_schema(field_type),
+                )
+            })
+            .collect::<Map<String, Value>>();
+        return json!({"type": "object", "properties": properties});
+    }
+
+    // Strip any precision/length arguments, e.g. decimal(10,2) or varchar(255)
+    let base_type = glue_type
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match base_type.as_str() {
+        "tinyint" | "smallint" | "int" | "integer" | "bigint" => json!({"type": "integer"}),
+        "float" | "double" | "decimal" => json!({"type": "number"}),
+        "boolean" => json!({"type": "boolean"}),
+        "date" => json!({"type": "string", "format": "date"}),
+        "timestamp" => json!({"type": "string", "format": "date-time"}),
+        "binary" => json!({"type": "string", "contentEncoding": "base64"}),
+        _ => json!({"type": "string"}),
+    }
+}
+
+/// Returns the text between the angle brackets of a parameterised type, e.g. `int` for
+/// `array<int>` when `name` is `array`
+fn type_arguments<'a>(glue_type: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = glue_type.get(..name.len())?;
+    if !prefix.eq_ignore_ascii_case(name) {
+        return None;
+    }
+    glue_type[name.len()..]
+        .trim_start()
+        .strip_prefix('<')?
+        .strip_suffix('>')
+}
+
+/// Splits on `separator` wherever it isn't nested inside `<...>` or `(...)`
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_types_map_to_json_schema_types() {
+        assert_eq!(
+            glue_type_to_json_schema("bigint"),
+            json!({"type": "integer"})
+        );
+        assert_eq!(glue_type_to_json_schema("INT"), json!({"type": "integer"}));
+        assert_eq!(
+            glue_type_to_json_schema("decimal(10,2)"),
+            json!({"type": "number"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("double"),
+            json!({"type": "number"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("boolean"),
+            json!({"type": "boolean"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("date"),
+            json!({"type": "string", "format": "date"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("timestamp"),
+            json!({"type": "string", "format": "date-time"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("binary"),
+            json!({"type": "string", "contentEncoding": "base64"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("varchar(255)"),
+            json!({"type": "string"})
+        );
+        assert_eq!(
+            glue_type_to_json_schema("geometry"),
+            json!({"type": "string"})
+        );
+    }
+
+    #[test]
+    fn nested_types_map_recursively() {
+        assert_eq!(
+            glue_type_to_json_schema("array<struct<id:bigint,tags:map<string,string>>>"),
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "integer"},
+                        "tags": {"type": "object", "additionalProperties": {"type": "string"}},
+                    },
+                },
+            })
+        );
+        assert_eq!(
+            glue_type_to_json_schema("struct<price: decimal(10,2), sold:boolean>"),
+            json!({
+                "type": "object",
+                "properties": {
+                    "price": {"type": "number"},
+                    "sold": {"type": "boolean"},
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_maps_fall_back_to_a_plain_object() {
+        assert_eq!(
+            glue_type_to_json_schema("map<string>"),
+            json!({"type": "object"})
+        );
+    }
+
+    #[test]
+    fn table_json_schema_describes_each_column() {
+        let columns = [
+            Column::builder()
+                .name("id")
+                .r#type("bigint")
+                .comment("Order ID")
+                .build()
+                .unwrap(),
+            Column::builder().name("notes").build().unwrap(),
+        ];
+
+        assert_eq!(
+            table_json_schema("orders", &columns),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "orders",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer", "description": "Order ID"},
+                    "notes": {"type": "string"},
+                },
+            })
+        );
+    }
+}
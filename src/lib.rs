@@ -1,203 +1,6235 @@
+mod access;
+mod builder;
+mod cache;
+mod confirmation;
+mod ddl;
+mod error;
+mod glue_api;
+mod json_schema;
+mod markdown;
+mod pagination;
+mod progress;
+mod rate_limit;
+mod resources;
+mod retry;
 pub mod util;
-use aws_config::BehaviorVersion;
-use metrics::counter;
-use rmcp::{Error as McpError, ServerHandler, const_string, model::*, schemars, tool};
+use access::DatabaseAccess;
+use aws_sdk_glue::config::http::HttpResponse;
+use aws_sdk_glue::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_glue::operation::get_table::GetTableOutput;
+use aws_sdk_glue::operation::{
+    batch_stop_job_run::BatchStopJobRunInput,
+    check_schema_version_validity::CheckSchemaVersionValidityInput,
+    delete_table::DeleteTableInput,
+    get_classifiers::GetClassifiersInput,
+    get_column_statistics_for_table::GetColumnStatisticsForTableInput,
+    get_connections::GetConnectionsInput,
+    get_crawler::{GetCrawlerError, GetCrawlerInput},
+    get_crawler_metrics::GetCrawlerMetricsInput,
+    get_crawlers::GetCrawlersInput,
+    get_data_catalog_encryption_settings::GetDataCatalogEncryptionSettingsInput,
+    get_data_quality_result::{GetDataQualityResultError, GetDataQualityResultInput},
+    get_database::{GetDatabaseError, GetDatabaseInput},
+    get_databases::GetDatabasesInput,
+    get_job::{GetJobError, GetJobInput},
+    get_job_run::{GetJobRunError, GetJobRunInput},
+    get_job_runs::GetJobRunsInput,
+    get_jobs::GetJobsInput,
+    get_ml_transforms::GetMlTransformsInput,
+    get_partition::GetPartitionInput,
+    get_partition_indexes::GetPartitionIndexesInput,
+    get_partitions::GetPartitionsInput,
+    get_resource_policy::GetResourcePolicyInput,
+    get_schema::{GetSchemaError, GetSchemaInput},
+    get_schema_by_definition::GetSchemaByDefinitionInput,
+    get_schema_version::{GetSchemaVersionError, GetSchemaVersionInput},
+    get_table::GetTableInput,
+    get_table_optimizer::GetTableOptimizerInput,
+    get_table_versions::GetTableVersionsInput,
+    get_tables::GetTablesInput,
+    get_tags::GetTagsInput,
+    get_triggers::GetTriggersInput,
+    get_user_defined_functions::GetUserDefinedFunctionsInput,
+    get_workflow::{GetWorkflowError, GetWorkflowInput},
+    list_data_quality_rulesets::ListDataQualityRulesetsInput,
+    list_registries::ListRegistriesInput,
+    list_sessions::ListSessionsInput,
+    search_tables::SearchTablesInput,
+    start_crawler::{StartCrawlerError, StartCrawlerInput},
+    start_job_run::{StartJobRunError, StartJobRunInput},
+    stop_crawler::{StopCrawlerError, StopCrawlerInput},
+    test_connection::TestConnectionInput,
+};
+use aws_sdk_glue::primitives::{DateTime, DateTimeFormat};
+use aws_sdk_glue::types::{
+    Classifier, ColumnStatisticsData, Crawler, CrawlerState, DataFormat,
+    DataQualityRulesetFilterCriteria, DataQualityTargetTable, DecimalNumber, Job, SchemaId,
+    SchemaVersionNumber, Table, TableOptimizerType,
+};
+use cache::{CacheKey, CatalogCache};
+use confirmation::{CONFIRMATION_TTL, Confirmations};
+use metrics::{counter, gauge, histogram};
+use pagination::{Paginated, fetch_page_from, paginate, paginate_limited, paginate_up_to};
+use resources::ResourceUri;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer, ServerHandler, model::*, schemars, tool};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio_util::task::TaskTracker;
+use tracing::Instrument;
+use tracing::field::Empty;
+
+pub use builder::GlueDataCatalogBuilder;
+pub use cache::DEFAULT_CACHE_TTL;
+pub use error::{ACCESS_DENIED, AWS_ERROR, GlueMcpError, THROTTLED, TIMEOUT};
+pub use glue_api::{GlueApi, GlueFuture};
+pub use retry::RetryPolicy;
+
+/// The largest page size Glue accepts for `GetDatabases` and `GetTables`
+const LIST_PAGE_LIMIT: i32 = 100;
+
+/// The largest page size Glue accepts for `SearchTables`
+const SEARCH_TABLES_PAGE_LIMIT: i32 = 1000;
+
+/// The largest page size Glue accepts for `GetJobRuns`
+const JOB_RUNS_PAGE_LIMIT: i32 = 200;
+
+/// The most column names Glue accepts in a single `GetColumnStatisticsForTable` request
+const COLUMN_STATISTICS_BATCH_LIMIT: usize = 100;
+
+/// The most tables whose metadata `get_database_full` and `get_tables_metadata` fetch
+/// concurrently, to stay clear of Glue's request rate limits
+const TABLE_METADATA_CONCURRENCY: usize = 10;
+
+/// The most S3 objects `get_table_storage_summary` lists when no limit is given, to bound the
+/// time spent enumerating huge tables
+const STORAGE_SUMMARY_MAX_OBJECTS: usize = 100_000;
+
+/// The most partitions `get_table_partition_summary` counts when no limit is given, to bound the
+/// time spent enumerating heavily partitioned tables
+const PARTITION_SUMMARY_MAX_PARTITIONS: usize = 10_000;
+
+/// The number of job runs returned by `get_job_runs` when no limit is given
+const DEFAULT_JOB_RUNS: i32 = 20;
+
+/// How a tool renders its result
+#[derive(Debug, Clone, Copy, Default, schemars::JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Structured JSON, for programmatic clients
+    #[default]
+    Json,
+    /// Compact Markdown, for reading in a chat UI
+    Markdown,
+}
+
+/// The order to sort names in
+#[derive(Debug, Clone, Copy, schemars::JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct EchoResult {
+    pub message: String,
+    pub timestamp: Option<String>,
+    pub server_version: String,
+}
 
 #[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
 pub struct ListDatabasesResult {
     pub databases: Vec<String>,
+    /// Whether more databases exist beyond the requested limit
+    pub truncated: bool,
+    /// Pass to `list_databases` to fetch the next page of databases
+    pub next_token: Option<String>,
+    /// How to see the databases left out to keep the response under the maximum size
+    pub note: Option<String>,
 }
 
 #[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
 pub struct DatabaseMetadata {
     pub name: String,
     pub tables: Vec<String>,
+    /// Whether more tables exist beyond the requested limit
+    pub truncated: bool,
+    /// Pass to `get_database_metadata` to fetch the next page of tables
+    pub next_token: Option<String>,
+    /// How to see the tables left out to keep the response under the maximum size
+    pub note: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ColumnMetadata {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl From<&aws_sdk_glue::types::Column> for ColumnMetadata {
+    fn from(col: &aws_sdk_glue::types::Column) -> Self {
+        Self {
+            name: col.name().into(),
+            data_type: col.r#type().map(Into::into),
+            comment: col.comment().map(Into::into),
+        }
+    }
 }
 
 #[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
 pub struct TableMetadata {
     pub name: String,
-    pub columns: Vec<String>,
+    /// e.g. `EXTERNAL_TABLE`, `MANAGED_TABLE` or `VIRTUAL_VIEW`
+    pub table_type: Option<String>,
+    pub columns: Vec<ColumnMetadata>,
+    pub partition_keys: Vec<ColumnMetadata>,
+    pub location: Option<String>,
+    pub serialization_library: Option<String>,
+    pub input_format: Option<String>,
+    pub output_format: Option<String>,
+    pub parameters: HashMap<String, String>,
+    /// The view's SQL as written, for `VIRTUAL_VIEW` tables
+    pub view_original_text: Option<String>,
+    /// The view's SQL with names fully expanded, for `VIRTUAL_VIEW` tables
+    pub view_expanded_text: Option<String>,
+    pub create_time: Option<String>,
+    /// When the table's definition last changed in the catalog
+    pub update_time: Option<String>,
+    /// When the table was last read, if Glue tracks it
+    pub last_access_time: Option<String>,
+    /// Whether columns were left out to keep the response under the maximum size
+    pub truncated: bool,
+    /// How to see the columns left out, when truncated
+    pub note: Option<String>,
 }
 
-#[derive(Debug, Clone)]
-pub struct GlueDataCatalog {
-    client: aws_sdk_glue::Client,
+/// The shape of a table without its column names, for exploring wide tables cheaply
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableSummary {
+    pub name: String,
+    pub table_type: Option<String>,
+    pub column_count: usize,
+    pub partition_key_count: usize,
+}
+
+impl From<TableMetadata> for TableSummary {
+    fn from(table: TableMetadata) -> Self {
+        Self {
+            name: table.name,
+            table_type: table.table_type,
+            column_count: table.columns.len(),
+            partition_key_count: table.partition_keys.len(),
+        }
+    }
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DatabaseDetails {
+    pub name: String,
+    pub description: Option<String>,
+    pub location_uri: Option<String>,
+    pub create_time: Option<String>,
+    pub parameters: HashMap<String, String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DatabaseFullMetadata {
+    pub name: String,
+    pub tables: Vec<TableMetadata>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct PartitionMetadata {
+    pub values: Vec<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct PartitionDetails {
+    pub database_name: String,
+    pub table_name: String,
+    pub values: Vec<String>,
+    pub location: Option<String>,
+    pub parameters: HashMap<String, String>,
+    pub creation_time: Option<String>,
+    pub last_access_time: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListPartitionsResult {
+    pub database_name: String,
+    pub table_name: String,
+    pub partitions: Vec<PartitionMetadata>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableReference {
+    pub database_name: String,
+    pub table_name: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SearchTablesResult {
+    pub tables: Vec<TableReference>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct CrawlerSummary {
+    pub name: String,
+    pub state: Option<String>,
+    pub last_crawl_status: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListCrawlersResult {
+    pub crawlers: Vec<CrawlerSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct S3TargetDetails {
+    pub path: Option<String>,
+    pub exclusions: Vec<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct CatalogTargetDetails {
+    pub database_name: String,
+    pub tables: Vec<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SchemaChangePolicyDetails {
+    pub update_behavior: Option<String>,
+    pub delete_behavior: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct CrawlerDetails {
+    pub name: String,
+    pub description: Option<String>,
+    pub role: Option<String>,
+    pub state: Option<String>,
+    pub database_name: Option<String>,
+    pub table_prefix: Option<String>,
+    pub schedule: Option<String>,
+    pub s3_targets: Vec<S3TargetDetails>,
+    pub catalog_targets: Vec<CatalogTargetDetails>,
+    pub schema_change_policy: Option<SchemaChangePolicyDetails>,
+    pub last_crawl_status: Option<String>,
+    pub last_crawl_error: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct StartCrawlerResult {
+    pub name: String,
+    pub message: String,
+    /// Whether the crawler was only checked, not started
+    pub dry_run: bool,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct JobSummary {
+    pub name: String,
+    pub role: Option<String>,
+    pub command_name: Option<String>,
+    pub glue_version: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListJobsResult {
+    pub jobs: Vec<JobSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct JobDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub role: Option<String>,
+    pub command_name: Option<String>,
+    pub script_location: Option<String>,
+    pub python_version: Option<String>,
+    pub glue_version: Option<String>,
+    /// The arguments passed to every run unless overridden, with secrets redacted
+    pub default_arguments: HashMap<String, String>,
+    pub worker_type: Option<String>,
+    pub number_of_workers: Option<i32>,
+    /// The maximum run time in minutes before a run is stopped
+    pub timeout_minutes: Option<i32>,
+    pub max_retries: i32,
+    /// The names of the Glue connections the job uses
+    pub connections: Vec<String>,
+    pub created_on: Option<String>,
+    pub last_modified_on: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct JobRunSummary {
+    pub id: String,
+    pub state: Option<String>,
+    pub started_on: Option<String>,
+    pub completed_on: Option<String>,
+    pub execution_time_seconds: i32,
+    pub error_message: Option<String>,
+}
+
+impl From<&aws_sdk_glue::types::JobRun> for JobRunSummary {
+    fn from(run: &aws_sdk_glue::types::JobRun) -> Self {
+        Self {
+            id: run.id().unwrap_or_default().into(),
+            state: run.job_run_state().map(|state| state.as_str().into()),
+            started_on: run.started_on().and_then(rfc3339),
+            completed_on: run.completed_on().and_then(rfc3339),
+            execution_time_seconds: run.execution_time(),
+            error_message: run.error_message().map(Into::into),
+        }
+    }
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetJobRunsResult {
+    pub job_name: String,
+    pub job_runs: Vec<JobRunSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetJobRunResult {
+    pub job_name: String,
+    pub job_run: JobRunSummary,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct StartJobRunResult {
+    pub job_name: String,
+    /// The ID of the started run, or `None` for a dry run
+    pub job_run_id: Option<String>,
+    pub message: String,
+    /// Whether the job was only checked, not started
+    pub dry_run: bool,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ConnectionSummary {
+    pub name: String,
+    pub connection_type: Option<String>,
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListConnectionsResult {
+    pub connections: Vec<ConnectionSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableVersionSummary {
+    pub version_id: String,
+    pub update_time: Option<String>,
+    pub columns: Vec<ColumnMetadata>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetTableVersionsResult {
+    pub database_name: String,
+    pub table_name: String,
+    pub versions: Vec<TableVersionSummary>,
+}
+
+/// Column statistics tagged by the kind of data Glue computed them for
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ColumnStatisticsSummary {
+    Boolean {
+        number_of_trues: i64,
+        number_of_falses: i64,
+        number_of_nulls: i64,
+    },
+    Date {
+        minimum_value: Option<String>,
+        maximum_value: Option<String>,
+        number_of_nulls: i64,
+        number_of_distinct_values: i64,
+    },
+    Decimal {
+        minimum_value: Option<f64>,
+        maximum_value: Option<f64>,
+        number_of_nulls: i64,
+        number_of_distinct_values: i64,
+    },
+    Double {
+        minimum_value: f64,
+        maximum_value: f64,
+        number_of_nulls: i64,
+        number_of_distinct_values: i64,
+    },
+    Long {
+        minimum_value: i64,
+        maximum_value: i64,
+        number_of_nulls: i64,
+        number_of_distinct_values: i64,
+    },
+    String {
+        maximum_length: i64,
+        average_length: f64,
+        number_of_nulls: i64,
+        number_of_distinct_values: i64,
+    },
+    Binary {
+        maximum_length: i64,
+        average_length: f64,
+        number_of_nulls: i64,
+    },
+}
+
+impl ColumnStatisticsSummary {
+    fn from_glue(data: &ColumnStatisticsData) -> Option<Self> {
+        if let Some(stats) = data.boolean_column_statistics_data() {
+            Some(Self::Boolean {
+                number_of_trues: stats.number_of_trues(),
+                number_of_falses: stats.number_of_falses(),
+                number_of_nulls: stats.number_of_nulls(),
+            })
+        } else if let Some(stats) = data.date_column_statistics_data() {
+            Some(Self::Date {
+                minimum_value: stats.minimum_value().and_then(rfc3339),
+                maximum_value: stats.maximum_value().and_then(rfc3339),
+                number_of_nulls: stats.number_of_nulls(),
+                number_of_distinct_values: stats.number_of_distinct_values(),
+            })
+        } else if let Some(stats) = data.decimal_column_statistics_data() {
+            Some(Self::Decimal {
+                minimum_value: stats.minimum_value().and_then(decimal_to_f64),
+                maximum_value: stats.maximum_value().and_then(decimal_to_f64),
+                number_of_nulls: stats.number_of_nulls(),
+                number_of_distinct_values: stats.number_of_distinct_values(),
+            })
+        } else if let Some(stats) = data.double_column_statistics_data() {
+            Some(Self::Double {
+                minimum_value: stats.minimum_value(),
+                maximum_value: stats.maximum_value(),
+                number_of_nulls: stats.number_of_nulls(),
+                number_of_distinct_values: stats.number_of_distinct_values(),
+            })
+        } else if let Some(stats) = data.long_column_statistics_data() {
+            Some(Self::Long {
+                minimum_value: stats.minimum_value(),
+                maximum_value: stats.maximum_value(),
+                number_of_nulls: stats.number_of_nulls(),
+                number_of_distinct_values: stats.number_of_distinct_values(),
+            })
+        } else if let Some(stats) = data.string_column_statistics_data() {
+            Some(Self::String {
+                maximum_length: stats.maximum_length(),
+                average_length: stats.average_length(),
+                number_of_nulls: stats.number_of_nulls(),
+                number_of_distinct_values: stats.number_of_distinct_values(),
+            })
+        } else {
+            data.binary_column_statistics_data()
+                .map(|stats| Self::Binary {
+                    maximum_length: stats.maximum_length(),
+                    average_length: stats.average_length(),
+                    number_of_nulls: stats.number_of_nulls(),
+                })
+        }
+    }
+}
+
+/// Converts Glue's big-endian two's complement decimal representation into a float, which is
+/// precise enough for summary statistics
+fn decimal_to_f64(decimal: &DecimalNumber) -> Option<f64> {
+    let bytes = decimal.unscaled_value().as_ref();
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0x00 };
+    let mut buffer = [fill; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(bytes);
+    let unscaled = i128::from_be_bytes(buffer);
+    Some(unscaled as f64 / 10f64.powi(decimal.scale()))
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ColumnStatisticsEntry {
+    pub column_name: String,
+    pub column_type: Option<String>,
+    pub analyzed_time: Option<String>,
+    pub stats: Option<ColumnStatisticsSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetColumnStatisticsResult {
+    pub database_name: String,
+    pub table_name: String,
+    pub columns: Vec<ColumnStatisticsEntry>,
+    pub failed: Vec<ColumnStatisticsFailure>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ColumnStatisticsFailure {
+    pub column_name: String,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct PartitionIndexSummary {
+    pub name: String,
+    pub keys: Vec<String>,
+    pub status: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetPartitionIndexesResult {
+    pub database_name: String,
+    pub table_name: String,
+    pub partition_indexes: Vec<PartitionIndexSummary>,
+}
+
+/// The outcome of describing one of the tables requested from `get_tables_metadata`
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TableMetadataEntry {
+    Table(TableMetadata),
+    Error { table_name: String, error: String },
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetTablesMetadataResult {
+    pub database_name: String,
+    pub tables: Vec<TableMetadataEntry>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct RegistrySummary {
+    pub name: String,
+    pub arn: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListRegistriesResult {
+    pub registries: Vec<RegistrySummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SchemaVersionDetails {
+    pub registry_name: String,
+    pub schema_name: String,
+    pub version_number: Option<i64>,
+    pub data_format: Option<String>,
+    pub status: Option<String>,
+    pub schema_definition: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetTagsResult {
+    pub resource_arn: String,
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct EncryptionSettings {
+    /// `DISABLED`, `SSE-KMS` or `SSE-KMS-WITH-SERVICE-ROLE`
+    pub catalog_encryption_mode: Option<String>,
+    pub catalog_kms_key_id: Option<String>,
+    pub connection_password_encrypted: bool,
+    pub connection_password_kms_key_id: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ResourcePolicyDetails {
+    /// The policy document, or `None` when no resource policy is configured
+    pub policy: Option<String>,
+    pub policy_hash: Option<String>,
+    pub create_time: Option<String>,
+    pub update_time: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableStorageSummary {
+    pub database_name: String,
+    pub table_name: String,
+    pub location: String,
+    pub object_count: usize,
+    pub total_size_bytes: i64,
+    /// Whether listing stopped at `max_objects`, leaving the counts incomplete
+    pub truncated: bool,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TriggerAction {
+    pub job_name: Option<String>,
+    pub crawler_name: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TriggerSummary {
+    pub name: String,
+    /// `SCHEDULED`, `CONDITIONAL`, `ON_DEMAND` or `EVENT`
+    #[serde(rename = "type")]
+    pub trigger_type: Option<String>,
+    pub state: Option<String>,
+    pub schedule: Option<String>,
+    pub workflow_name: Option<String>,
+    pub actions: Vec<TriggerAction>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListTriggersResult {
+    pub triggers: Vec<TriggerSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct WorkflowNode {
+    pub id: Option<String>,
+    /// `CRAWLER`, `JOB` or `TRIGGER`
+    #[serde(rename = "type")]
+    pub node_type: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct WorkflowEdge {
+    pub source_id: Option<String>,
+    pub destination_id: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct WorkflowRunSummary {
+    pub status: Option<String>,
+    pub started_on: Option<String>,
+    pub completed_on: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct WorkflowDetails {
+    pub name: String,
+    pub description: Option<String>,
+    pub nodes: Vec<WorkflowNode>,
+    pub edges: Vec<WorkflowEdge>,
+    pub last_run: Option<WorkflowRunSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ClassifierSummary {
+    pub name: String,
+    /// `Grok`, `XML`, `JSON` or `Csv`
+    #[serde(rename = "type")]
+    pub classifier_type: String,
+    pub classification: Option<String>,
+    /// The fields that decide which files the classifier matches and how it reads them, such as
+    /// a Grok pattern, XML row tag, JSON path or CSV delimiter
+    pub config: HashMap<String, String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListClassifiersResult {
+    pub classifiers: Vec<ClassifierSummary>,
+}
+
+/// Summarizes whichever kind of classifier is set, or `None` if Glue returned none of them
+fn classifier_summary(classifier: &Classifier) -> Option<ClassifierSummary> {
+    let config = |fields: &[(&str, Option<String>)]| {
+        fields
+            .iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value.clone()?)))
+            .collect()
+    };
+
+    if let Some(grok) = classifier.grok_classifier() {
+        return Some(ClassifierSummary {
+            name: grok.name().into(),
+            classifier_type: "Grok".into(),
+            classification: Some(grok.classification().into()),
+            config: config(&[
+                ("grok_pattern", Some(grok.grok_pattern().into())),
+                ("custom_patterns", grok.custom_patterns().map(Into::into)),
+            ]),
+        });
+    }
+    if let Some(xml) = classifier.xml_classifier() {
+        return Some(ClassifierSummary {
+            name: xml.name().into(),
+            classifier_type: "XML".into(),
+            classification: Some(xml.classification().into()),
+            config: config(&[("row_tag", xml.row_tag().map(Into::into))]),
+        });
+    }
+    if let Some(json) = classifier.json_classifier() {
+        return Some(ClassifierSummary {
+            name: json.name().into(),
+            classifier_type: "JSON".into(),
+            classification: None,
+            config: config(&[("json_path", Some(json.json_path().into()))]),
+        });
+    }
+    let csv = classifier.csv_classifier()?;
+    Some(ClassifierSummary {
+        name: csv.name().into(),
+        classifier_type: "Csv".into(),
+        classification: None,
+        config: config(&[
+            ("delimiter", csv.delimiter().map(Into::into)),
+            ("quote_symbol", csv.quote_symbol().map(Into::into)),
+            (
+                "contains_header",
+                csv.contains_header().map(|header| header.as_str().into()),
+            ),
+            (
+                "header",
+                (!csv.header().is_empty()).then(|| csv.header().join(",")),
+            ),
+            (
+                "custom_datatypes",
+                (!csv.custom_datatypes().is_empty()).then(|| csv.custom_datatypes().join(",")),
+            ),
+        ]),
+    })
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct FunctionResource {
+    /// `JAR`, `FILE` or `ARCHIVE`
+    pub resource_type: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct UserDefinedFunctionSummary {
+    pub name: String,
+    pub class_name: Option<String>,
+    pub owner_name: Option<String>,
+    pub resource_uris: Vec<FunctionResource>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListUserDefinedFunctionsResult {
+    pub database_name: String,
+    pub functions: Vec<UserDefinedFunctionSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DataQualityRulesetSummary {
+    pub name: String,
+    pub description: Option<String>,
+    pub rule_count: Option<i32>,
+    pub database_name: Option<String>,
+    pub table_name: Option<String>,
+    pub created_on: Option<String>,
+    pub last_modified_on: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListDataQualityRulesetsResult {
+    pub rulesets: Vec<DataQualityRulesetSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DataQualityRuleOutcome {
+    pub name: String,
+    /// `PASS`, `FAIL` or `ERROR`
+    pub result: Option<String>,
+    pub evaluation_message: Option<String>,
+    /// The metric values the rule was evaluated against, e.g. `Column.id.Completeness`
+    pub evaluated_metrics: HashMap<String, f64>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DataQualityResultDetails {
+    pub result_id: String,
+    pub ruleset_name: Option<String>,
+    pub database_name: Option<String>,
+    pub table_name: Option<String>,
+    /// The fraction of rules that passed, from 0 to 1
+    pub score: Option<f64>,
+    pub started_on: Option<String>,
+    pub completed_on: Option<String>,
+    pub job_name: Option<String>,
+    pub job_run_id: Option<String>,
+    pub rule_results: Vec<DataQualityRuleOutcome>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: String,
+    /// `PROVISIONING`, `READY`, `FAILED`, `TIMEOUT`, `STOPPING` or `STOPPED`
+    pub status: Option<String>,
+    pub created_on: Option<String>,
+    pub role: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListSessionsResult {
+    pub sessions: Vec<SessionSummary>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableOptimizerRunSummary {
+    /// `starting`, `in_progress`, `completed` or `failed`
+    pub event_type: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableOptimizerDetails {
+    pub database_name: String,
+    pub table_name: String,
+    /// `compaction`, `retention` or `orphan_file_deletion`
+    pub optimizer_type: String,
+    /// Whether an optimizer of this type is configured for the table
+    pub configured: bool,
+    pub enabled: Option<bool>,
+    pub role_arn: Option<String>,
+    pub last_run: Option<TableOptimizerRunSummary>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct CrawlerMetricsSummary {
+    pub crawler_name: String,
+    pub median_runtime_seconds: f64,
+    pub last_runtime_seconds: f64,
+    /// The estimated time left in the current run, while `still_estimating` is false
+    pub time_left_seconds: f64,
+    pub still_estimating: bool,
+    pub tables_created: i32,
+    pub tables_updated: i32,
+    pub tables_deleted: i32,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct GetCrawlerMetricsResult {
+    pub crawlers: Vec<CrawlerMetricsSummary>,
+    /// Requested crawlers that Glue returned no metrics for
+    pub missing: Vec<String>,
 }
 
-#[tool(tool_box)]
-impl GlueDataCatalog {
-    #[allow(dead_code)]
-    pub fn new(client: aws_sdk_glue::Client) -> Self {
-        Self { client }
-    }
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct StopCrawlerResult {
+    pub name: String,
+    pub message: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct JobRunStopFailure {
+    pub job_run_id: String,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct BatchStopJobRunResult {
+    pub job_name: String,
+    /// The runs Glue accepted a stop request for
+    pub stopped: Vec<String>,
+    pub failed: Vec<JobRunStopFailure>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TablePartitionSummary {
+    pub database_name: String,
+    pub table_name: String,
+    pub partition_count: usize,
+    /// Whether counting stopped at `max_partitions`, leaving the count incomplete
+    pub truncated: bool,
+    /// The values of the most recently created partition among those counted
+    pub latest_partition_values: Option<Vec<String>>,
+    pub latest_partition_created_on: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ConfirmationRequired {
+    /// What will happen once confirmed
+    pub action: String,
+    /// Pass back as `confirmation_token` to carry out the action
+    pub confirmation_token: String,
+    pub expires_in_seconds: u64,
+    pub message: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct DeleteTableResult {
+    pub database_name: String,
+    pub table_name: String,
+    pub message: String,
+}
+
+/// The tools that change state in AWS, or, like `test_connection`, act on resources outside the
+/// catalog, which are only available when mutations are enabled
+const MUTATING_TOOLS: &[&str] = &[
+    "start_crawler",
+    "stop_crawler",
+    "start_job_run",
+    "batch_stop_job_run",
+    "delete_table",
+    "test_connection",
+];
+
+/// How to narrow a listing that was cut short to the maximum response size, using the parameters
+/// `tool` takes, or `None` if it takes none that narrow `list_field`, as for a table's columns
+fn narrowing_hint(tool: &str, list_field: &str) -> Option<&'static str> {
+    match (tool, list_field) {
+        ("list_databases", _) => Some("page through them with limit and next_token"),
+        ("get_database_metadata", _) => Some(
+            "filter them with name_prefix or table_type, or page through them with limit and next_token",
+        ),
+        ("describe", "tables") => Some(
+            "use get_database_metadata, which filters tables by name_prefix and pages through them",
+        ),
+        ("search_tables", _) => Some("use a more specific keyword or a smaller max_results"),
+        ("get_job_runs", _) => Some("ask for fewer runs with max_results"),
+        ("get_partitions", _) => Some("filter them with an expression"),
+        ("get_column_statistics", _) => Some("ask for fewer column_names at a time"),
+        ("get_tables_metadata", _) => Some("ask for fewer table_names at a time"),
+        ("get_crawler_metrics", _) => Some("ask for fewer crawler_names at a time"),
+        _ => None,
+    }
+}
+
+/// Groups a tool by the part of Glue it works with, for `describe_capabilities`
+fn tool_category(tool: &str) -> &'static str {
+    match tool {
+        "echo" | "describe_capabilities" => "server",
+        "list_crawlers"
+        | "get_crawler"
+        | "get_crawler_metrics"
+        | "start_crawler"
+        | "stop_crawler"
+        | "list_classifiers" => "crawlers",
+        "list_jobs" | "get_job" | "get_job_runs" | "get_job_run" | "start_job_run"
+        | "batch_stop_job_run" | "list_triggers" | "get_workflow" | "list_sessions" => "jobs",
+        "list_registries"
+        | "get_schema_version"
+        | "get_schema_by_definition"
+        | "check_schema_version_validity" => "schema_registry",
+        "list_data_quality_rulesets" | "get_data_quality_result" => "data_quality",
+        "list_ml_transforms" => "ml_transforms",
+        "get_tags" | "get_data_catalog_encryption_settings" | "get_resource_policy" => "security",
+        _ => "catalog",
+    }
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ToolCapability {
+    pub name: String,
+    /// Whether the tool changes state in AWS
+    pub mutation: bool,
+    /// Whether the tool can be called on this server, which mutations can't unless enabled
+    pub available: bool,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct CapabilitiesDescription {
+    pub server_version: String,
+    pub region: Option<String>,
+    /// The catalog queried when a tool isn't given a catalog ID, if known without calling AWS.
+    /// `None` means the caller's AWS account
+    pub catalog_id: Option<String>,
+    pub caching_enabled: bool,
+    pub mutations_enabled: bool,
+    pub auth_required: bool,
+    pub case_insensitive_names: bool,
+    /// Whether the server only touches the databases permitted by its allow and deny lists
+    pub databases_restricted: bool,
+    /// The tools grouped by the part of Glue they work with
+    pub tools: BTreeMap<String, Vec<ToolCapability>>,
+}
+
+/// A crawler or job that may populate a table, and the evidence suggesting it does
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LineageSource {
+    pub name: String,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct TableLineage {
+    pub database_name: String,
+    pub table_name: String,
+    pub location: Option<String>,
+    /// Always true: the sources are inferred from names and locations, not recorded by Glue
+    pub heuristic: bool,
+    pub note: String,
+    /// Crawlers whose targets include the table or overlap its location, except those touching
+    /// databases the server's allow and deny lists don't permit
+    pub crawlers: Vec<LineageSource>,
+    /// Jobs whose arguments mention the table, its database or a location overlapping its own,
+    /// except those naming databases the server's allow and deny lists don't permit
+    pub jobs: Vec<LineageSource>,
+}
+
+/// Whether two S3 locations are in the same bucket and one contains the other
+fn s3_locations_overlap(a: &str, b: &str) -> bool {
+    match (parse_s3_location(a), parse_s3_location(b)) {
+        (Some((bucket_a, prefix_a)), Some((bucket_b, prefix_b))) => {
+            bucket_a == bucket_b
+                && (prefix_a.starts_with(&prefix_b) || prefix_b.starts_with(&prefix_a))
+        }
+        _ => false,
+    }
+}
+
+/// Why a crawler looks like it populates the given table, if it does at all
+fn crawler_lineage_reasons(
+    crawler: &Crawler,
+    database_name: &str,
+    table_name: &str,
+    location: Option<&str>,
+) -> Vec<String> {
+    let into_database = crawler
+        .database_name()
+        .is_some_and(|name| name.eq_ignore_ascii_case(database_name));
+    let targets = crawler.targets();
+    let mut reasons = Vec::new();
+
+    for target in targets
+        .map(|targets| targets.catalog_targets())
+        .unwrap_or_default()
+    {
+        if target.database_name().eq_ignore_ascii_case(database_name)
+            && target
+                .tables()
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(table_name))
+        {
+            reasons.push("Its catalog target lists the table".into());
+        }
+    }
+
+    for path in targets
+        .map(|targets| targets.s3_targets())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|target| target.path())
+    {
+        if location.is_some_and(|location| s3_locations_overlap(path, location)) {
+            reasons.push(if into_database {
+                format!(
+                    "It crawls {path}, overlapping the table's location, into the table's database"
+                )
+            } else {
+                format!("It crawls {path}, overlapping the table's location")
+            });
+        }
+    }
+
+    // Only a weak hint, so given just when nothing stronger links the crawler to the table
+    let matching_prefix = crawler.table_prefix().filter(|prefix| {
+        reasons.is_empty()
+            && into_database
+            && table_name
+                .to_lowercase()
+                .starts_with(&prefix.to_lowercase())
+    });
+    if let Some(prefix) = matching_prefix {
+        reasons.push(format!(
+            "It creates tables prefixed '{prefix}' in the table's database"
+        ));
+    }
+    reasons
+}
+
+/// Why a job's arguments suggest it reads or writes the given table, one reason per argument
+fn job_lineage_reasons(
+    job: &Job,
+    database_name: &str,
+    table_name: &str,
+    location: Option<&str>,
+) -> Vec<String> {
+    let qualified_name = format!("{database_name}.{table_name}").to_lowercase();
+    let mut reasons = job
+        .default_arguments()
+        .into_iter()
+        .chain(job.non_overridable_arguments())
+        .flatten()
+        .filter_map(|(key, value)| {
+            if location.is_some_and(|location| s3_locations_overlap(value, location)) {
+                Some(format!(
+                    "Its argument {key} is an S3 location overlapping the table's location"
+                ))
+            } else if value.to_lowercase().contains(&qualified_name) {
+                Some(format!(
+                    "Its argument {key} mentions {database_name}.{table_name}"
+                ))
+            } else if value.eq_ignore_ascii_case(database_name) {
+                Some(format!("Its argument {key} names the table's database"))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<String>>();
+    // Arguments are unordered, so sort them to keep the result stable between calls
+    reasons.sort();
+    reasons
+}
+
+/// The databases a crawler writes to or reads the tables of
+fn crawler_databases(crawler: &Crawler) -> impl Iterator<Item = &str> {
+    crawler.database_name().into_iter().chain(
+        crawler
+            .targets()
+            .map(|targets| targets.catalog_targets())
+            .unwrap_or_default()
+            .iter()
+            .map(|target| target.database_name()),
+    )
+}
+
+/// The databases a job's arguments name, going by arguments such as `--database` holding a
+/// database name, or `--source_table` holding a `database.table` name
+fn job_databases(job: &Job) -> impl Iterator<Item = &str> {
+    job.default_arguments()
+        .into_iter()
+        .chain(job.non_overridable_arguments())
+        .flatten()
+        .filter_map(|(key, value)| {
+            let key = key.to_lowercase();
+            if key.contains("database") {
+                Some(value.as_str())
+            } else if key.contains("table") {
+                value
+                    .split_once('.')
+                    .map(|(database_name, _)| database_name)
+            } else {
+                None
+            }
+        })
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SchemaDefinitionMatch {
+    pub registry_name: String,
+    pub schema_name: String,
+    /// Whether a version of the schema is registered with exactly the given definition
+    pub matched: bool,
+    pub schema_version_id: Option<String>,
+    pub status: Option<String>,
+    pub data_format: Option<String>,
+    pub created_time: Option<String>,
+    /// The compatibility mode new versions of the schema must satisfy, e.g. `BACKWARD`
+    pub compatibility: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct SchemaValidity {
+    pub data_format: String,
+    pub valid: bool,
+    /// Why the definition is invalid, if it is
+    pub error: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ConnectionTestResult {
+    pub connection_name: String,
+    /// Whether Glue reached the data source with the connection's settings and credentials
+    pub reachable: bool,
+    /// `SUCCEEDED` or `FAILED`
+    pub status: String,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct MlTransformSummary {
+    pub id: String,
+    pub name: Option<String>,
+    /// The kind of machine learning transform, e.g. `FIND_MATCHES`
+    #[serde(rename = "type")]
+    pub transform_type: Option<String>,
+    /// `NOT_READY`, `READY` or `DELETING`
+    pub status: Option<String>,
+    pub description: Option<String>,
+    pub created_on: Option<String>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct ListMlTransformsResult {
+    pub transforms: Vec<MlTransformSummary>,
+}
+
+/// Copies the given properties, replacing the value of any whose key looks like it holds a
+/// password or secret
+fn redact_secrets<'a>(
+    properties: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> HashMap<String, String> {
+    properties
+        .into_iter()
+        .map(|(key, value)| {
+            let upper_key = key.to_ascii_uppercase();
+            let value = if upper_key.contains("PASSWORD") || upper_key.contains("SECRET") {
+                "***"
+            } else {
+                value
+            };
+            (key.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Splits an `s3://bucket/prefix` location (or its `s3a://` and `s3n://` variants) into the
+/// bucket and a prefix ending in `/`, so that sibling locations sharing a name prefix don't match
+fn parse_s3_location(location: &str) -> Option<(&str, String)> {
+    let path = ["s3://", "s3a://", "s3n://"]
+        .iter()
+        .find_map(|scheme| location.strip_prefix(scheme))?;
+    let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+    if bucket.is_empty() {
+        return None;
+    }
+    let prefix = match prefix {
+        "" => String::new(),
+        prefix if prefix.ends_with('/') => prefix.into(),
+        prefix => format!("{prefix}/"),
+    };
+    Some((bucket, prefix))
+}
+
+/// Formats an AWS timestamp as an RFC3339 string
+fn rfc3339(timestamp: &DateTime) -> Option<String> {
+    timestamp.fmt(DateTimeFormat::DateTime).ok()
+}
+
+/// The AWS partition a region belongs to, which prefixes the ARNs of its resources
+fn aws_partition(region: &str) -> &'static str {
+    if region.starts_with("cn-") {
+        "aws-cn"
+    } else if region.starts_with("us-gov-") {
+        "aws-us-gov"
+    } else if region.starts_with("us-isob-") {
+        "aws-iso-b"
+    } else if region.starts_with("us-iso-") {
+        "aws-iso"
+    } else {
+        "aws"
+    }
+}
+
+/// The ARN of the Data Catalog `catalog_id` in `region`
+fn catalog_arn(region: &str, catalog_id: &str) -> String {
+    let partition = aws_partition(region);
+    format!("arn:{partition}:glue:{region}:{catalog_id}:catalog")
+}
+
+/// Validates an optional `limit` parameter, returning the maximum number of items to collect
+fn parse_limit(tool: &str, limit: Option<i32>) -> Result<Option<usize>, McpError> {
+    match limit {
+        Some(limit) if limit <= 0 => Err(GlueMcpError::invalid_params(
+            "limit must be greater than zero",
+            json!({"limit": limit}),
+        )
+        .into_mcp(tool)),
+        limit => Ok(limit.map(|limit| limit as usize)),
+    }
+}
+
+/// Narrows the tables listed for a database. Filters are applied to each page Glue returns
+#[derive(Debug, Default)]
+struct TableFilter {
+    /// Only tables whose names start with this prefix, ignoring case
+    name_prefix: Option<String>,
+    /// Only tables of this type, e.g. `EXTERNAL_TABLE` or `VIRTUAL_VIEW`, ignoring case
+    table_type: Option<String>,
+}
+
+impl TableFilter {
+    fn matches(&self, table: &Table) -> bool {
+        self.name_prefix.as_ref().is_none_or(|prefix| {
+            table
+                .name()
+                .to_lowercase()
+                .starts_with(&prefix.to_lowercase())
+        }) && self.table_type.as_ref().is_none_or(|table_type| {
+            table
+                .table_type()
+                .is_some_and(|actual| actual.eq_ignore_ascii_case(table_type))
+        })
+    }
+}
+
+/// The page size to request from Glue when collecting at most `limit` items
+fn page_size(limit: Option<usize>) -> Option<i32> {
+    limit.map(|limit| limit.min(LIST_PAGE_LIMIT as usize) as i32)
+}
+
+/// Tool parameters recorded on each call's span, when present, so that its logs can be found by
+/// the catalog object they concern
+const SPAN_PARAMS: &[&str] = &["database_name", "table_name", "name", "job_name"];
+
+/// Generates an ID that correlates the logs and any error response of a single tool call
+fn new_request_id() -> String {
+    format!("{:016x}", RandomState::new().hash_one(Instant::now()))
+}
+
+/// Picks the candidate equal to `name` ignoring case. Returns `None` if there isn't one, and a
+/// not-found error listing the candidates if several differ from `name` only in case
+fn unique_case_insensitive_match(
+    tool: &str,
+    kind: &str,
+    name: &str,
+    candidates: Vec<String>,
+) -> Result<Option<String>, McpError> {
+    let mut matches = candidates
+        .into_iter()
+        .filter(|candidate| candidate.to_lowercase() == name.to_lowercase())
+        .collect::<Vec<String>>();
+    match matches.len() {
+        0 | 1 => Ok(matches.pop()),
+        _ => Err(GlueMcpError::not_found(
+            format!(
+                "{kind} '{name}' does not exist, and several names differ from it only in case"
+            ),
+            json!({"name": name, "candidates": matches}),
+        )
+        .into_mcp(tool)),
+    }
+}
+
+/// Sorts the names in the `field` array of a result alphabetically, if an order was requested
+fn sort_names(result: &mut Value, field: &str, sort: Option<SortOrder>) {
+    let (Some(sort), Some(names)) = (sort, result.get_mut(field).and_then(Value::as_array_mut))
+    else {
+        return;
+    };
+    names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    if let SortOrder::Desc = sort {
+        names.reverse();
+    }
+}
+
+/// Records how many items a tool returns to the `result_size.<tool>` histogram, to spot responses
+/// large enough to crowd out an agent's context
+fn record_result_size(tool: &str, items: usize) {
+    histogram!(format!("result_size.{tool}")).record(items as f64);
+}
+
+/// Records the length of the `field` array of a tool's result, as [`record_result_size`] does
+fn record_result_field_size(tool: &str, result: &Value, field: &str) {
+    record_result_size(
+        tool,
+        result
+            .get(field)
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len),
+    );
+}
+
+/// Returns a tool's JSON result as is, or rendered as Markdown when requested
+fn formatted_result<T: DeserializeOwned>(
+    tool: &str,
+    result: Value,
+    format: Option<OutputFormat>,
+    render_markdown: impl FnOnce(&T) -> String,
+) -> Result<CallToolResult, McpError> {
+    match format.unwrap_or_default() {
+        OutputFormat::Json => Ok(CallToolResult::success(vec![Content::json(result)?])),
+        OutputFormat::Markdown => {
+            let result = serde_json::from_value::<T>(result)
+                .map_err(|e| GlueMcpError::serialization(e).into_mcp(tool))?;
+            Ok(CallToolResult::success(vec![Content::text(
+                render_markdown(&result),
+            )]))
+        }
+    }
+}
+
+/// Serializes a tool's result to JSON, counting failures against `tool`
+fn to_json<T: Serialize>(tool: &str, result: &T) -> Result<Value, McpError> {
+    serde_json::to_value(result).map_err(|e| GlueMcpError::serialization(e).into_mcp(tool))
+}
+
+/// Wraps a tool's result as JSON content
+fn json_result<T: Serialize>(tool: &str, result: &T) -> Result<CallToolResult, McpError> {
+    Ok(CallToolResult::success(vec![Content::json(to_json(
+        tool, result,
+    )?)?]))
+}
+
+/// How long a single AWS request may take before it's abandoned, unless overridden
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many AWS requests may be in flight at once across all tool calls, unless overridden
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 20;
+
+/// The largest serialized response a listing tool returns, unless overridden
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+/// Room left for the fields added to a result cut short to fit the maximum response size
+const RESPONSE_NOTE_MARGIN: usize = 512;
+
+/// The instructions sent to clients on initialization, unless overridden
+pub const DEFAULT_INSTRUCTIONS: &str = "This server provides a glue data catalog tool that can be used to get database and table metadata from an AWS Glue Data Catalog";
+
+/// Environment variable that must be set to `true` (or `1`) to enable tools that change state in
+/// AWS, such as starting crawlers or job runs
+pub const ALLOW_MUTATIONS_ENV: &str = "GLUE_MCP_ALLOW_MUTATIONS";
+
+/// How long after rebuilding the AWS clients with fresh credentials they won't be rebuilt again,
+/// so that a burst of rejected calls reloads the configuration only once
+const CREDENTIAL_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The AWS clients, rebuilt together when their credentials are rejected
+#[derive(Debug)]
+struct AwsClients {
+    glue: Arc<dyn GlueApi>,
+    s3: Option<aws_sdk_s3::Client>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GlueDataCatalog {
+    clients: Arc<RwLock<AwsClients>>,
+    /// How the clients were configured, so that they can be rebuilt with fresh credentials.
+    /// `None` when the catalog was built around an existing client
+    reconnect: Option<Arc<GlueDataCatalogBuilder>>,
+    last_credential_refresh: Arc<Mutex<Option<Instant>>>,
+    allow_mutations: bool,
+    cache: Option<CatalogCache>,
+    retry_policy: RetryPolicy,
+    request_timeout: Duration,
+    max_concurrent_requests: usize,
+    request_permits: Arc<Semaphore>,
+    max_response_bytes: usize,
+    case_insensitive_names: bool,
+    database_access: DatabaseAccess,
+    instructions: Option<String>,
+    auth_required: bool,
+    confirmations: Confirmations,
+    in_flight: TaskTracker,
+}
+
+impl GlueDataCatalog {
+    fn glue(&self) -> Arc<dyn GlueApi> {
+        self.clients
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .glue
+            .clone()
+    }
+
+    fn s3_client(&self) -> Option<aws_sdk_s3::Client> {
+        self.clients
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .s3
+            .clone()
+    }
+
+    /// Rebuilds the AWS clients from a freshly loaded configuration after `tool` was refused for
+    /// expired or unrecognised credentials, so that later calls pick up renewed ones. Does nothing
+    /// for a catalog built around an existing client, or if the clients were rebuilt within the
+    /// last [`CREDENTIAL_REFRESH_INTERVAL`]. Each rebuild is counted as `credential_refreshes`
+    async fn refresh_credentials(&self, tool: &str) {
+        let Some(builder) = &self.reconnect else {
+            return;
+        };
+        let mut last_refresh = self.last_credential_refresh.lock().await;
+        if last_refresh.is_some_and(|at| at.elapsed() < CREDENTIAL_REFRESH_INTERVAL) {
+            return;
+        }
+
+        log::warn!(
+            "AWS rejected the credentials used by {}; reloading the AWS configuration",
+            tool
+        );
+        let (glue, s3) = builder.clients().await;
+        *self.clients.write().unwrap_or_else(PoisonError::into_inner) = AwsClients {
+            glue: Arc::new(glue),
+            s3: Some(s3),
+        };
+        *last_refresh = Some(Instant::now());
+        counter!("credential_refreshes").increment(1);
+    }
+
+    fn mutations_allowed_from_env() -> bool {
+        std::env::var(ALLOW_MUTATIONS_ENV)
+            .map(|value| matches!(value.to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false)
+    }
+
+    /// Sends an AWS request on behalf of `tool`, retrying throttled and transient failures
+    /// according to the configured [`RetryPolicy`]. Each attempt waits for one of the shared
+    /// request permits, then is abandoned with a timeout error once the request timeout elapses.
+    /// The elapsed time, including any retries, is recorded in milliseconds to the
+    /// `latency.<tool>` histogram whether or not the call succeeds
+    async fn send_with_retry<T, E, F, Fut>(
+        &self,
+        tool: &str,
+        send: F,
+    ) -> Result<T, SdkError<E, HttpResponse>>
+    where
+        E: ProvideErrorMetadata,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+    {
+        self.send_retrying(tool, retry::is_retryable, send).await
+    }
+
+    /// Sends an AWS request that changes state on behalf of `tool`, like
+    /// [`send_with_retry`](Self::send_with_retry) but only retrying when Glue throttled it. A
+    /// request that timed out or failed transiently may still have been carried out, so retrying
+    /// it could e.g. start a second job run
+    async fn send_mutation<T, E, F, Fut>(
+        &self,
+        tool: &str,
+        send: F,
+    ) -> Result<T, SdkError<E, HttpResponse>>
+    where
+        E: ProvideErrorMetadata,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+    {
+        self.send_retrying(tool, retry::is_throttled, send).await
+    }
+
+    async fn send_retrying<T, E, F, Fut>(
+        &self,
+        tool: &str,
+        retryable: fn(&SdkError<E, HttpResponse>) -> bool,
+        mut send: F,
+    ) -> Result<T, SdkError<E, HttpResponse>>
+    where
+        E: ProvideErrorMetadata,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+    {
+        let start = Instant::now();
+        let timeout = self.request_timeout;
+        let result = retry::send_with_retry(&self.retry_policy, tool, retryable, || {
+            let attempt = send();
+            async move {
+                let permit = self.request_permits.acquire().await;
+                self.record_permits_in_use();
+                let result = tokio::time::timeout(timeout, attempt)
+                    .await
+                    .unwrap_or_else(|elapsed| Err(SdkError::timeout_error(elapsed)));
+                drop(permit);
+                self.record_permits_in_use();
+                result
+            }
+        })
+        .await;
+        histogram!(format!("latency.{tool}")).record(start.elapsed().as_secs_f64() * 1000.0);
+        if result.as_ref().is_err_and(error::is_credentials_error) {
+            self.refresh_credentials(tool).await;
+        }
+        result
+    }
+
+    /// Publishes how many request permits are taken to the `aws_requests.in_use` gauge
+    fn record_permits_in_use(&self) {
+        let in_use = self.max_concurrent_requests - self.request_permits.available_permits();
+        gauge!("aws_requests.in_use").set(in_use as f64);
+    }
+
+    /// Returns the cached result for `key`, if caching is enabled and the entry hasn't expired
+    async fn cached(&self, tool: &str, key: &CacheKey) -> Option<Value> {
+        let cache = self.cache.as_ref()?;
+        let value = cache.get(key).await;
+        match value {
+            Some(_) => counter!(format!("cache_hits.{tool}")).increment(1),
+            None => counter!(format!("cache_misses.{tool}")).increment(1),
+        }
+        value
+    }
+
+    async fn cache_result(&self, key: CacheKey, value: &Value) {
+        if let Some(cache) = &self.cache {
+            cache.insert(key, value.clone()).await;
+        }
+    }
+
+    /// Cuts the `list_field` array of a listing tool's result short if the serialized result is
+    /// larger than the maximum response size, marking the result as truncated and noting how
+    /// many items were left out. The next token is dropped too, as it would skip the items cut.
+    /// Each cut is counted as `truncated_responses.<tool>`
+    fn limit_response_size(&self, tool: &str, mut result: Value, list_field: &str) -> Value {
+        if result.to_string().len() <= self.max_response_bytes {
+            return result;
+        }
+        let items = match result.get_mut(list_field) {
+            Some(Value::Array(items)) => std::mem::take(items),
+            _ => return result,
+        };
+
+        let total = items.len();
+        let mut size = result.to_string().len() + RESPONSE_NOTE_MARGIN;
+        let items = items
+            .into_iter()
+            .take_while(|item| {
+                // Each item after the first is preceded by a comma
+                size += item.to_string().len() + 1;
+                size <= self.max_response_bytes
+            })
+            .collect::<Vec<Value>>();
+
+        log::warn!(
+            "Returning {} of {} {} from {} to stay under {} bytes",
+            items.len(),
+            total,
+            list_field,
+            tool,
+            self.max_response_bytes
+        );
+        counter!(format!("truncated_responses.{tool}")).increment(1);
+        let mut note = format!(
+            "Only {} of {total} {list_field} are included, to keep the response under {} bytes",
+            items.len(),
+            self.max_response_bytes
+        );
+        if let Some(hint) = narrowing_hint(tool, list_field) {
+            note.push_str(". To see the rest, ");
+            note.push_str(hint);
+        }
+        result["note"] = note.into();
+        result[list_field] = Value::Array(items);
+        result["truncated"] = true.into();
+        if let Some(next_token) = result.get_mut("next_token") {
+            *next_token = Value::Null;
+        }
+        result
+    }
+
+    /// Wraps a listing tool's result as JSON content, cut short to the maximum response size
+    fn list_result<T: Serialize>(
+        &self,
+        tool: &str,
+        result: &T,
+        list_field: &str,
+    ) -> Result<CallToolResult, McpError> {
+        let result = self.limit_response_size(tool, to_json(tool, result)?, list_field);
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    /// Describes several tables concurrently on behalf of `tool`, returning each table's metadata
+    /// or error in the order the tables were given
+    async fn fetch_tables_metadata(
+        &self,
+        tool: &'static str,
+        database_name: &str,
+        table_names: Vec<String>,
+        catalog_id: Option<String>,
+    ) -> Vec<Result<TableMetadata, McpError>> {
+        let semaphore = Arc::new(Semaphore::new(TABLE_METADATA_CONCURRENCY));
+        let mut requests = JoinSet::new();
+        for (index, table_name) in table_names.into_iter().enumerate() {
+            let catalog = self.clone();
+            let semaphore = semaphore.clone();
+            let database_name = database_name.to_string();
+            let catalog_id = catalog_id.clone();
+            requests.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let table = catalog
+                    .fetch_table_metadata(tool, database_name, table_name, catalog_id)
+                    .await
+                    .and_then(|metadata| {
+                        serde_json::from_value::<TableMetadata>(metadata)
+                            .map_err(|e| GlueMcpError::serialization(e).into_mcp(tool))
+                    });
+                (index, table)
+            });
+        }
+
+        // Results arrive in completion order, so restore the order the tables were given in
+        let mut tables = requests.join_all().await;
+        tables.sort_by_key(|(index, _)| *index);
+        tables.into_iter().map(|(_, table)| table).collect()
+    }
+
+    /// Sends a `GetTable` request on behalf of `tool`. With case-insensitive names enabled, a
+    /// table that isn't found is retried once under the names that match it ignoring case
+    async fn get_table(
+        &self,
+        tool: &str,
+        database_name: &str,
+        table_name: &str,
+        catalog_id: Option<String>,
+    ) -> Result<GetTableOutput, McpError> {
+        self.ensure_database_allowed(tool, database_name)?;
+        let get_table = |database_name: String, table_name: String| {
+            let request = GetTableInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name)
+                .name(table_name);
+            async move {
+                self.send_with_retry(tool, || self.glue().get_table(request.clone()))
+                    .await
+            }
+        };
+
+        match get_table(database_name.into(), table_name.into()).await {
+            Err(e) if self.case_insensitive_names && error::is_not_found(&e) => {
+                match self
+                    .resolve_table_name(tool, database_name, table_name, catalog_id.clone())
+                    .await?
+                {
+                    Some((database_name, table_name)) => get_table(database_name, table_name).await,
+                    None => Err(e),
+                }
+            }
+            result => result,
+        }
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get table metadata",
+                &format!("Table '{database_name}.{table_name}'"),
+                json!({"database_name": database_name, "table_name": table_name}),
+                &e,
+            )
+            .into_mcp(tool)
+        })
+    }
+
+    /// Finds the database whose name matches `database_name` ignoring case, if there is exactly one
+    async fn resolve_database_name(
+        &self,
+        tool: &str,
+        database_name: &str,
+        catalog_id: Option<String>,
+    ) -> Result<Option<String>, McpError> {
+        let databases = self.fetch_databases(tool, None, None, catalog_id).await?;
+        let ListDatabasesResult { databases, .. } = serde_json::from_value(databases)
+            .map_err(|e| GlueMcpError::serialization(e).into_mcp(tool))?;
+        unique_case_insensitive_match(tool, "Database", database_name, databases)
+    }
+
+    /// Finds the database and table whose names match the given ones ignoring case, if there is
+    /// exactly one of each
+    async fn resolve_table_name(
+        &self,
+        tool: &str,
+        database_name: &str,
+        table_name: &str,
+        catalog_id: Option<String>,
+    ) -> Result<Option<(String, String)>, McpError> {
+        // Resolves the database name too, if it needs it
+        let metadata = self
+            .fetch_database_metadata(
+                tool,
+                database_name.into(),
+                TableFilter {
+                    name_prefix: Some(table_name.into()),
+                    ..TableFilter::default()
+                },
+                None,
+                None,
+                catalog_id,
+            )
+            .await?;
+        let DatabaseMetadata { name, tables, .. } = serde_json::from_value(metadata)
+            .map_err(|e| GlueMcpError::serialization(e).into_mcp(tool))?;
+        Ok(
+            unique_case_insensitive_match(tool, "Table", table_name, tables)?
+                .map(|table_name| (name, table_name)),
+        )
+    }
+
+    /// Rejects a call on behalf of `tool` touching a database the server's allow and deny lists
+    /// don't permit
+    fn ensure_database_allowed(&self, tool: &str, database_name: &str) -> Result<(), McpError> {
+        if self.database_access.permits(database_name) {
+            return Ok(());
+        }
+        Err(GlueMcpError::access_denied(format!(
+            "Database '{database_name}' is not accessible through this server"
+        ))
+        .into_mcp(tool))
+    }
+
+    /// Rejects a call to a mutating tool unless mutations have been enabled
+    fn ensure_mutations_allowed(&self, tool: &str) -> Result<(), McpError> {
+        if self.allow_mutations {
+            return Ok(());
+        }
+        Err(GlueMcpError::invalid_request(
+            format!(
+                "The {tool} tool changes state in AWS and mutations are disabled; set {ALLOW_MUTATIONS_ENV}=true to enable it"
+            ),
+            Some(json!({"reason": "mutations_disabled"})),
+        )
+        .into_mcp(tool))
+    }
+
+    /// The two-phase confirmation shared by destructive tools. Without a token, issues one for
+    /// `action` and returns the result asking the caller to echo it back. With a token, checks
+    /// that it was issued for exactly `action` and consumes it, returning `None` so the tool can
+    /// proceed
+    async fn confirm_destructive(
+        &self,
+        tool: &str,
+        action: String,
+        confirmation_token: Option<String>,
+    ) -> Result<Option<CallToolResult>, McpError> {
+        let Some(token) = confirmation_token else {
+            let confirmation_token = self.confirmations.issue(&action).await.map_err(|e| {
+                McpError::internal_error(
+                    format!("Failed to generate a confirmation token: {e}"),
+                    None,
+                )
+            })?;
+            let result = ConfirmationRequired {
+                message: format!(
+                    "Nothing has been changed yet. To proceed, call {tool} again with the same parameters and this confirmation_token"
+                ),
+                action,
+                confirmation_token,
+                expires_in_seconds: CONFIRMATION_TTL.as_secs(),
+            };
+            return json_result(tool, &result).map(Some);
+        };
+
+        if self.confirmations.redeem(&token, &action).await {
+            counter!(format!("confirmations.{tool}")).increment(1);
+            return Ok(None);
+        }
+        Err(GlueMcpError::invalid_params(
+            "The confirmation token is invalid, expired, already used or was issued for a different action; call again without a token to get a new one",
+            json!({"confirmation_token": token, "action": action}),
+        )
+        .into_mcp(tool))
+    }
+
+    /// Lists up to `limit` database names in the catalog on behalf of `tool`, serving from the
+    /// cache when possible. Given a `next_token`, lists only the page it continues from
+    async fn fetch_databases(
+        &self,
+        tool: &str,
+        limit: Option<usize>,
+        next_token: Option<String>,
+        catalog_id: Option<String>,
+    ) -> Result<Value, McpError> {
+        let cache_key = CacheKey::Databases {
+            catalog_id: catalog_id.clone(),
+            limit,
+            next_token: next_token.clone(),
+        };
+        if let Some(cached) = self.cached(tool, &cache_key).await {
+            return Ok(cached);
+        }
+
+        let fetch_page = |page_token| {
+            let request = GetDatabasesInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .set_max_results(page_size(limit))
+                .set_next_token(page_token);
+            async move {
+                self.send_with_retry(tool, || self.glue().get_databases(request.clone()))
+                    .await
+                    .map(|response| {
+                        let names = response
+                            .database_list()
+                            .iter()
+                            .map(|db| db.name())
+                            .filter(|name| self.database_access.permits(name))
+                            .map(Into::into)
+                            .collect::<Vec<String>>();
+                        (names, response.next_token().map(Into::into))
+                    })
+            }
+        };
+        let Paginated {
+            items: databases,
+            truncated,
+            next_token,
+        } = match next_token {
+            Some(token) => fetch_page_from(token, fetch_page).await,
+            None => paginate_limited(limit.unwrap_or(usize::MAX), fetch_page).await,
+        }
+        .map_err(|e| GlueMcpError::from_aws("Failed to list databases", &e).into_mcp(tool))?;
+
+        let result = ListDatabasesResult {
+            databases,
+            truncated,
+            next_token,
+            note: None,
+        };
+        let json_result = to_json(tool, &result)?;
+        self.cache_result(cache_key, &json_result).await;
+
+        Ok(json_result)
+    }
+
+    /// Lists up to `limit` tables in a database on behalf of `tool`, keeping only those matching
+    /// `filter`, and serving from the cache when possible. Given a `next_token`, lists only the
+    /// page it continues from
+    async fn fetch_database_metadata(
+        &self,
+        tool: &str,
+        database_name: String,
+        filter: TableFilter,
+        limit: Option<usize>,
+        next_token: Option<String>,
+        catalog_id: Option<String>,
+    ) -> Result<Value, McpError> {
+        self.ensure_database_allowed(tool, &database_name)?;
+        let cache_key = CacheKey::DatabaseMetadata {
+            catalog_id: catalog_id.clone(),
+            database_name: database_name.clone(),
+            name_prefix: filter
+                .name_prefix
+                .as_ref()
+                .map(|prefix| prefix.to_lowercase()),
+            table_type: filter
+                .table_type
+                .as_ref()
+                .map(|table_type| table_type.to_uppercase()),
+            limit,
+            next_token: next_token.clone(),
+        };
+        if let Some(cached) = self.cached(tool, &cache_key).await {
+            return Ok(cached);
+        }
+
+        let filter = &filter;
+        let list_tables = |database_name: String| {
+            let catalog_id = &catalog_id;
+            let next_token = &next_token;
+            async move {
+                let fetch_page = |page_token| {
+                    let request = GetTablesInput::builder()
+                        .set_catalog_id(catalog_id.clone())
+                        .database_name(database_name.clone())
+                        .set_max_results(page_size(limit))
+                        .set_next_token(page_token);
+                    async move {
+                        self.send_with_retry(tool, || self.glue().get_tables(request.clone()))
+                            .await
+                            .map(|response| {
+                                let names = response
+                                    .table_list()
+                                    .iter()
+                                    .filter(|table| filter.matches(table))
+                                    .map(|table| table.name().into())
+                                    .collect::<Vec<String>>();
+                                (names, response.next_token().map(Into::into))
+                            })
+                    }
+                };
+                match next_token.clone() {
+                    Some(token) => fetch_page_from(token, fetch_page).await,
+                    None => paginate_limited(limit.unwrap_or(usize::MAX), fetch_page).await,
+                }
+            }
+        };
+
+        let mut database_name = database_name;
+        let Paginated {
+            items: tables,
+            truncated,
+            next_token,
+        } = match list_tables(database_name.clone()).await {
+            Err(e) if self.case_insensitive_names && error::is_not_found(&e) => {
+                match self
+                    .resolve_database_name(tool, &database_name, catalog_id.clone())
+                    .await?
+                {
+                    Some(resolved) => {
+                        database_name = resolved;
+                        list_tables(database_name.clone()).await
+                    }
+                    None => Err(e),
+                }
+            }
+            result => result,
+        }
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get tables",
+                &format!("Database '{database_name}'"),
+                json!({"database_name": database_name}),
+                &e,
+            )
+            .into_mcp(tool)
+        })?;
+
+        let result = DatabaseMetadata {
+            name: database_name,
+            tables,
+            truncated,
+            next_token,
+            note: None,
+        };
+
+        let json_result = to_json(tool, &result)?;
+        self.cache_result(cache_key, &json_result).await;
+
+        Ok(json_result)
+    }
+
+    /// Describes a table on behalf of `tool`, serving from the cache when possible
+    async fn fetch_table_metadata(
+        &self,
+        tool: &str,
+        database_name: String,
+        table_name: String,
+        catalog_id: Option<String>,
+    ) -> Result<Value, McpError> {
+        let cache_key = CacheKey::TableMetadata {
+            catalog_id: catalog_id.clone(),
+            database_name: database_name.clone(),
+            table_name: table_name.clone(),
+        };
+        if let Some(cached) = self.cached(tool, &cache_key).await {
+            return Ok(cached);
+        }
+
+        let response = self
+            .get_table(tool, &database_name, &table_name, catalog_id)
+            .await?;
+
+        let table = response.table();
+        let table_name = table.map_or(table_name, |table| table.name().into());
+        let storage_descriptor = table.and_then(|table| table.storage_descriptor());
+
+        let columns = storage_descriptor
+            .map(|sd| sd.columns())
+            .unwrap_or_default()
+            .iter()
+            .map(ColumnMetadata::from)
+            .collect::<Vec<ColumnMetadata>>();
+
+        let partition_keys = table
+            .map(|table| table.partition_keys())
+            .unwrap_or_default()
+            .iter()
+            .map(ColumnMetadata::from)
+            .collect::<Vec<ColumnMetadata>>();
+
+        log::info!(
+            "Got {} columns and {} partition keys for table {}",
+            columns.len(),
+            partition_keys.len(),
+            table_name
+        );
+
+        let result = TableMetadata {
+            name: table_name,
+            table_type: table.and_then(|table| table.table_type()).map(Into::into),
+            columns,
+            partition_keys,
+            location: storage_descriptor
+                .and_then(|sd| sd.location())
+                .map(Into::into),
+            serialization_library: storage_descriptor
+                .and_then(|sd| sd.serde_info())
+                .and_then(|serde| serde.serialization_library())
+                .map(Into::into),
+            input_format: storage_descriptor
+                .and_then(|sd| sd.input_format())
+                .map(Into::into),
+            output_format: storage_descriptor
+                .and_then(|sd| sd.output_format())
+                .map(Into::into),
+            parameters: table
+                .and_then(|table| table.parameters())
+                .cloned()
+                .unwrap_or_default(),
+            view_original_text: table
+                .and_then(|table| table.view_original_text())
+                .map(Into::into),
+            view_expanded_text: table
+                .and_then(|table| table.view_expanded_text())
+                .map(Into::into),
+            create_time: table
+                .and_then(|table| table.create_time())
+                .and_then(rfc3339),
+            update_time: table
+                .and_then(|table| table.update_time())
+                .and_then(rfc3339),
+            last_access_time: table
+                .and_then(|table| table.last_access_time())
+                .and_then(rfc3339),
+            truncated: false,
+            note: None,
+        };
+
+        let json_result = to_json(tool, &result)?;
+        self.cache_result(cache_key, &json_result).await;
+
+        Ok(json_result)
+    }
+}
+
+#[tool(tool_box)]
+impl GlueDataCatalog {
+    /// Starts configuring a catalog, loading the AWS configuration when built
+    pub fn builder() -> GlueDataCatalogBuilder {
+        GlueDataCatalogBuilder::new()
+    }
+
+    /// Creates a catalog backed by an existing Glue client, with the default settings
+    pub fn new(client: aws_sdk_glue::Client) -> Self {
+        Self::builder().build_with_client(client)
+    }
+
+    /// Overrides how throttled and transiently failing AWS calls are retried
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Overrides how long a single AWS request may take before it's abandoned
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Overrides how many AWS requests may be in flight at once. Further requests wait for one to
+    /// finish rather than failing
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self.request_permits = Arc::new(Semaphore::new(max_concurrent_requests));
+        self
+    }
+
+    /// Sets the S3 client used to read the data under table locations
+    pub fn with_s3_client(mut self, s3_client: aws_sdk_s3::Client) -> Self {
+        self.clients = Arc::new(RwLock::new(AwsClients {
+            glue: self.glue(),
+            s3: Some(s3_client),
+        }));
+        self
+    }
+
+    /// Enables resolving database and table names that Glue doesn't recognise by matching them
+    /// against the existing names ignoring case. Names are matched exactly by default
+    pub fn with_case_insensitive_names(mut self, case_insensitive_names: bool) -> Self {
+        self.case_insensitive_names = case_insensitive_names;
+        self
+    }
+
+    /// Overrides the instructions sent to clients on initialization, e.g. to describe the
+    /// account's naming conventions or which databases to prefer
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Records that clients must authenticate to reach this catalog, as reported by
+    /// `describe_capabilities`. Authentication itself is enforced by the transport
+    pub fn with_auth_required(mut self, auth_required: bool) -> Self {
+        self.auth_required = auth_required;
+        self
+    }
+
+    /// Enables caching of database, database metadata and table metadata reads, each entry
+    /// living for `ttl` or [`DEFAULT_CACHE_TTL`] when `None`
+    pub fn with_cache(mut self, ttl: Option<Duration>) -> Self {
+        self.cache = Some(cache::new_cache(ttl.unwrap_or(DEFAULT_CACHE_TTL)));
+        self
+    }
+
+    /// Creates a new GlueDataCatalog using the default AWS configuration from environment
+    pub async fn from_env() -> anyhow::Result<Self> {
+        Self::builder().build().await
+    }
+
+    /// Creates a new GlueDataCatalog for the given region, ignoring any region set in the
+    /// environment
+    pub async fn from_region(region: &str) -> anyhow::Result<Self> {
+        Self::builder().region(region).build().await
+    }
+
+    /// Creates a new GlueDataCatalog that sends requests to `endpoint_url` instead of the real AWS
+    /// endpoint, e.g. a LocalStack instance for testing. Everything else is configured from the
+    /// environment
+    pub async fn from_endpoint_url(endpoint_url: &str) -> anyhow::Result<Self> {
+        Self::builder().endpoint_url(endpoint_url).build().await
+    }
+
+    /// Creates a new GlueDataCatalog using the named profile from the shared AWS config and
+    /// credentials files
+    pub async fn from_profile(profile_name: &str) -> anyhow::Result<Self> {
+        Self::builder().profile_name(profile_name).build().await
+    }
+
+    /// Creates a new GlueDataCatalog that assumes the IAM role `role_arn` for its requests, e.g.
+    /// to query another account's catalog. The credentials used to assume it are configured from
+    /// the environment
+    pub async fn from_assumed_role(role_arn: String, session_name: String) -> anyhow::Result<Self> {
+        Self::builder()
+            .assume_role(role_arn, session_name)
+            .build()
+            .await
+    }
+
+    /// The number of tool calls currently being handled, across all clones of this catalog
+    pub fn in_flight_calls(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Waits up to `timeout` for in-flight tool calls to finish, returning how many are still
+    /// running when it gives up
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        self.in_flight.close();
+        let _ = tokio::time::timeout(timeout, self.in_flight.wait()).await;
+        self.in_flight.len()
+    }
+
+    /// Lists the databases in the caller's catalog into the cache, so that the first
+    /// `list_databases` call doesn't wait on Glue. Returns how many databases were listed, or
+    /// `None` without calling Glue if caching is disabled
+    pub async fn prefetch_databases(&self) -> anyhow::Result<Option<usize>> {
+        if self.cache.is_none() {
+            return Ok(None);
+        }
+        let result = self
+            .fetch_databases("prefetch_databases", None, None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to prefetch databases: {}", e.message))?;
+        Ok(Some(result["databases"].as_array().map_or(0, Vec::len)))
+    }
+
+    /// Checks that AWS Glue is reachable with the configured credentials by requesting a single
+    /// database
+    pub async fn ping(&self) -> anyhow::Result<()> {
+        self.glue()
+            .get_databases(GetDatabasesInput::builder().max_results(1))
+            .await?;
+        Ok(())
+    }
+
+    #[tool(
+        description = "Echo a message back along with the server time and version, without calling AWS. Useful for checking connectivity to the server"
+    )]
+    async fn echo(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The message to echo back")]
+        message: String,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.echo").increment(1);
+
+        let result = EchoResult {
+            message,
+            timestamp: rfc3339(&DateTime::from(SystemTime::now())),
+            server_version: env!("CARGO_PKG_VERSION").into(),
+        };
+
+        json_result("echo", &result)
+    }
+
+    #[tool(description = "List the databases in an AWS Glue Data Catalog")]
+    async fn list_databases(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The maximum number of databases to return, defaulting to all of them"
+        )]
+        limit: Option<i32>,
+        #[tool(param)]
+        #[schemars(
+            description = "The next_token from a previous call, to fetch just the page that follows it rather than all pages"
+        )]
+        next_token: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "Sort the names alphabetically, \"asc\" or \"desc\", instead of in the order Glue returns them"
+        )]
+        sort: Option<SortOrder>,
+        #[tool(param)]
+        #[schemars(
+            description = "How to render the result: \"json\" (the default) or \"markdown\" for a compact, human-readable table"
+        )]
+        format: Option<OutputFormat>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Listing databases in {}",
+            self.glue()
+                .region()
+                .as_deref()
+                .unwrap_or("an unresolved region")
+        );
+        counter!("calls.list_databases").increment(1);
+
+        let limit = parse_limit("list_databases", limit)?;
+        let mut result = self
+            .fetch_databases("list_databases", limit, next_token, catalog_id)
+            .await?;
+        sort_names(&mut result, "databases", sort);
+        let result = self.limit_response_size("list_databases", result, "databases");
+        record_result_field_size("list_databases", &result, "databases");
+        formatted_result("list_databases", result, format, markdown::databases)
+    }
+
+    #[tool(
+        description = "Get the attributes of a database in an AWS Glue Data Catalog, including its description, location URI and parameters, but not its tables"
+    )]
+    async fn get_database(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting database {}", database_name);
+        counter!("calls.get_database").increment(1);
+
+        let not_found = || {
+            GlueMcpError::not_found(
+                format!("Database '{database_name}' does not exist"),
+                json!({"database_name": database_name}),
+            )
+        };
+
+        let get_database = |database_name: String| {
+            let request = GetDatabaseInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .name(database_name);
+            async move {
+                self.send_with_retry("get_database", || self.glue().get_database(request.clone()))
+                    .await
+            }
+        };
+        let response = match get_database(database_name.clone()).await {
+            Err(e) if self.case_insensitive_names && error::is_not_found(&e) => {
+                match self
+                    .resolve_database_name("get_database", &database_name, catalog_id.clone())
+                    .await?
+                {
+                    Some(resolved) => get_database(resolved).await,
+                    None => Err(e),
+                }
+            }
+            result => result,
+        };
+        let response = response
+            .map_err(|e| match e.as_service_error() {
+                Some(GetDatabaseError::EntityNotFoundException(_)) => not_found(),
+                _ => GlueMcpError::from_aws("Failed to get database", &e),
+            })
+            .map_err(|e| e.into_mcp("get_database"))?;
+
+        let database = response
+            .database()
+            .ok_or_else(|| not_found().into_mcp("get_database"))?;
+
+        let result = DatabaseDetails {
+            name: database.name().into(),
+            description: database.description().map(Into::into),
+            location_uri: database.location_uri().map(Into::into),
+            create_time: database.create_time().and_then(rfc3339),
+            parameters: database.parameters().cloned().unwrap_or_default(),
+        };
+
+        json_result("get_database", &result)
+    }
+
+    #[tool(
+        description = "Get database metadata from an AWS Glue Data Catalog, including the tables in the database"
+    )]
+    async fn get_database_metadata(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "Only return tables whose names start with this prefix, ignoring case"
+        )]
+        name_prefix: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "Only return tables of this type, e.g. \"EXTERNAL_TABLE\", \"MANAGED_TABLE\" or \"VIRTUAL_VIEW\", ignoring case"
+        )]
+        table_type: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The maximum number of tables to return, defaulting to all of them"
+        )]
+        limit: Option<i32>,
+        #[tool(param)]
+        #[schemars(
+            description = "The next_token from a previous call, to fetch just the page that follows it rather than all pages"
+        )]
+        next_token: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "Sort the names alphabetically, \"asc\" or \"desc\", instead of in the order Glue returns them"
+        )]
+        sort: Option<SortOrder>,
+        #[tool(param)]
+        #[schemars(
+            description = "How to render the result: \"json\" (the default) or \"markdown\" for a compact, human-readable table"
+        )]
+        format: Option<OutputFormat>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting tables for database {}", database_name);
+        counter!("calls.get_database_metadata").increment(1);
+
+        let limit = parse_limit("get_database_metadata", limit)?;
+        let mut result = self
+            .fetch_database_metadata(
+                "get_database_metadata",
+                database_name,
+                TableFilter {
+                    name_prefix,
+                    table_type,
+                },
+                limit,
+                next_token,
+                catalog_id,
+            )
+            .await?;
+        sort_names(&mut result, "tables", sort);
+        let result = self.limit_response_size("get_database_metadata", result, "tables");
+        record_result_field_size("get_database_metadata", &result, "tables");
+        formatted_result(
+            "get_database_metadata",
+            result,
+            format,
+            markdown::database_metadata,
+        )
+    }
+
+    #[tool(
+        description = "Get table metadata from an AWS Glue Data Catalog, including the columns, their types and the partition keys of the table"
+    )]
+    async fn get_table_metadata(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "How to render the result: \"json\" (the default) or \"markdown\" for a compact, human-readable table"
+        )]
+        format: Option<OutputFormat>,
+        #[tool(param)]
+        #[schemars(
+            description = "Return only the table type and the number of columns and partition keys, as JSON, instead of the full column list. Defaults to false"
+        )]
+        summary_only: Option<bool>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting columns for table {}", table_name);
+        counter!("calls.get_table_metadata").increment(1);
+
+        let result = self
+            .fetch_table_metadata("get_table_metadata", database_name, table_name, catalog_id)
+            .await?;
+        if summary_only.unwrap_or(false) {
+            let table = serde_json::from_value::<TableMetadata>(result)
+                .map_err(|e| GlueMcpError::serialization(e).into_mcp("get_table_metadata"))?;
+            return json_result("get_table_metadata", &TableSummary::from(table));
+        }
+        let result = self.limit_response_size("get_table_metadata", result, "columns");
+        record_result_field_size("get_table_metadata", &result, "columns");
+        formatted_result(
+            "get_table_metadata",
+            result,
+            format,
+            markdown::table_metadata,
+        )
+    }
+
+    #[tool(
+        description = "Describe a table or database in an AWS Glue Data Catalog by its fully-qualified name: \"database.table\" returns the table's metadata, as get_table_metadata does, and a name without a dot returns the database's tables, as get_database_metadata does"
+    )]
+    async fn describe(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The fully-qualified name, e.g. \"sales.orders\" for a table or \"sales\" for a database. Everything after the first dot is the table name"
+        )]
+        fqn: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "How to render the result: \"json\" (the default) or \"markdown\" for a compact, human-readable table"
+        )]
+        format: Option<OutputFormat>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Describing {}", fqn);
+        counter!("calls.describe").increment(1);
+
+        let (database_name, table_name) = match fqn.split_once('.') {
+            Some((database_name, table_name)) => (database_name, Some(table_name)),
+            None => (fqn.as_str(), None),
+        };
+        if database_name.trim().is_empty() || table_name.is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(GlueMcpError::invalid_params(
+                "fqn must be a database name, or a database and table name separated by a dot, e.g. \"sales.orders\"",
+                json!({"fqn": fqn}),
+            )
+            .into_mcp("describe"));
+        }
+
+        match table_name {
+            Some(table_name) => {
+                let result = self
+                    .fetch_table_metadata(
+                        "describe",
+                        database_name.into(),
+                        table_name.into(),
+                        catalog_id,
+                    )
+                    .await?;
+                let result = self.limit_response_size("describe", result, "columns");
+                record_result_field_size("describe", &result, "columns");
+                formatted_result("describe", result, format, markdown::table_metadata)
+            }
+            None => {
+                let result = self
+                    .fetch_database_metadata(
+                        "describe",
+                        database_name.into(),
+                        TableFilter::default(),
+                        None,
+                        None,
+                        catalog_id,
+                    )
+                    .await?;
+                let result = self.limit_response_size("describe", result, "tables");
+                record_result_field_size("describe", &result, "tables");
+                formatted_result("describe", result, format, markdown::database_metadata)
+            }
+        }
+    }
+
+    #[tool(
+        description = "Get the full metadata of every table in an AWS Glue database, including each table's columns, partition keys and storage, in a single call"
+    )]
+    async fn get_database_full(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting all table metadata for database {}", database_name);
+        counter!("calls.get_database_full").increment(1);
+
+        let metadata = self
+            .fetch_database_metadata(
+                "get_database_full",
+                database_name.clone(),
+                TableFilter::default(),
+                None,
+                None,
+                catalog_id.clone(),
+            )
+            .await?;
+        let DatabaseMetadata { tables, .. } = serde_json::from_value(metadata)
+            .map_err(|e| GlueMcpError::serialization(e).into_mcp("get_database_full"))?;
+
+        let tables = self
+            .fetch_tables_metadata("get_database_full", &database_name, tables, catalog_id)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<TableMetadata>, McpError>>()?;
+
+        log::info!(
+            "Got metadata for {} tables in database {}",
+            tables.len(),
+            database_name
+        );
+
+        let result = DatabaseFullMetadata {
+            name: database_name,
+            tables,
+        };
+
+        record_result_size("get_database_full", result.tables.len());
+        self.list_result("get_database_full", &result, "tables")
+    }
+
+    #[tool(
+        description = "Get the partitions of a table in an AWS Glue Data Catalog, including each partition's values and S3 location"
+    )]
+    async fn get_partitions(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "An optional Glue partition filter expression, e.g. year='2024' AND month='01'"
+        )]
+        expression: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting partitions for table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_partitions").increment(1);
+
+        let partitions = paginate(|next_token| {
+            let request = GetPartitionsInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .table_name(table_name.clone())
+                .set_expression(expression.clone())
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_partitions", || {
+                    self.glue().get_partitions(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let partitions = response
+                        .partitions()
+                        .iter()
+                        .map(|partition| PartitionMetadata {
+                            values: partition.values().to_vec(),
+                            location: partition
+                                .storage_descriptor()
+                                .and_then(|sd| sd.location())
+                                .map(Into::into),
+                        })
+                        .collect::<Vec<PartitionMetadata>>();
+                    (partitions, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get partitions",
+                &format!("Table '{database_name}.{table_name}'"),
+                json!({"database_name": database_name, "table_name": table_name}),
+                &e,
+            )
+            .into_mcp("get_partitions")
+        })?;
+
+        log::info!(
+            "Got {} partitions for table {}.{}",
+            partitions.len(),
+            database_name,
+            table_name
+        );
+
+        let result = ListPartitionsResult {
+            database_name,
+            table_name,
+            partitions,
+        };
+
+        record_result_size("get_partitions", result.partitions.len());
+        self.list_result("get_partitions", &result, "partitions")
+    }
+
+    #[tool(
+        description = "Get a single partition of a table in an AWS Glue Data Catalog by its values, including its S3 location and parameters"
+    )]
+    async fn get_partition(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The partition's values, in the order of the table's partition keys, e.g. [\"2024\", \"01\"]"
+        )]
+        partition_values: Vec<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting partition {:?} of table {}.{}",
+            partition_values,
+            database_name,
+            table_name
+        );
+        counter!("calls.get_partition").increment(1);
+
+        if partition_values.is_empty() {
+            return Err(GlueMcpError::invalid_params(
+                "partition_values must contain a value for each partition key",
+                json!({"partition_values": partition_values}),
+            )
+            .into_mcp("get_partition"));
+        }
+
+        let request = GetPartitionInput::builder()
+            .set_catalog_id(catalog_id)
+            .database_name(database_name.clone())
+            .table_name(table_name.clone())
+            .set_partition_values(Some(partition_values.clone()));
+        let response = self
+            .send_with_retry("get_partition", || {
+                self.glue().get_partition(request.clone())
+            })
+            .await
+            .map_err(|e| {
+                GlueMcpError::from_aws_or_not_found(
+                    "Failed to get partition",
+                    &format!(
+                        "Partition {partition_values:?} of table '{database_name}.{table_name}'"
+                    ),
+                    json!({
+                        "database_name": database_name,
+                        "table_name": table_name,
+                        "partition_values": partition_values,
+                    }),
+                    &e,
+                )
+                .into_mcp("get_partition")
+            })?;
+
+        let partition = response.partition();
+        let result = PartitionDetails {
+            values: partition
+                .map(|partition| partition.values().to_vec())
+                .unwrap_or(partition_values),
+            location: partition
+                .and_then(|partition| partition.storage_descriptor())
+                .and_then(|sd| sd.location())
+                .map(Into::into),
+            parameters: partition
+                .and_then(|partition| partition.parameters())
+                .cloned()
+                .unwrap_or_default(),
+            creation_time: partition
+                .and_then(|partition| partition.creation_time())
+                .and_then(rfc3339),
+            last_access_time: partition
+                .and_then(|partition| partition.last_access_time())
+                .and_then(rfc3339),
+            database_name,
+            table_name,
+        };
+
+        json_result("get_partition", &result)
+    }
+
+    #[tool(
+        description = "Search for tables across all databases in an AWS Glue Data Catalog by keyword, matching table names, descriptions and column metadata"
+    )]
+    async fn search_tables(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The keyword to search for")]
+        keyword: String,
+        #[tool(param)]
+        #[schemars(description = "The maximum number of matching tables to return")]
+        max_results: Option<i32>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Searching tables for '{}'", keyword);
+        counter!("calls.search_tables").increment(1);
+
+        let max_items = match max_results {
+            Some(max) if max <= 0 => {
+                return Err(GlueMcpError::invalid_params(
+                    "max_results must be greater than zero",
+                    json!({"max_results": max}),
+                )
+                .into_mcp("search_tables"));
+            }
+            Some(max) => max as usize,
+            None => usize::MAX,
+        };
+
+        let tables = paginate_up_to(max_items, |next_token| {
+            let request = SearchTablesInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .search_text(keyword.clone())
+                .set_max_results(max_results.map(|max| max.min(SEARCH_TABLES_PAGE_LIMIT)))
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("search_tables", || {
+                    self.glue().search_tables(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let tables = response
+                        .table_list()
+                        .iter()
+                        .filter(|table| {
+                            self.database_access
+                                .permits(table.database_name().unwrap_or_default())
+                        })
+                        .map(|table| TableReference {
+                            database_name: table.database_name().unwrap_or_default().into(),
+                            table_name: table.name().into(),
+                        })
+                        .collect::<Vec<TableReference>>();
+                    (tables, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to search tables", &e).into_mcp("search_tables")
+        })?;
+
+        log::info!("Found {} tables matching '{}'", tables.len(), keyword);
+
+        let result = SearchTablesResult { tables };
+
+        record_result_size("search_tables", result.tables.len());
+        self.list_result("search_tables", &result, "tables")
+    }
+
+    #[tool(
+        description = "Get a Hive/Athena CREATE TABLE DDL statement for a table in an AWS Glue Data Catalog"
+    )]
+    async fn get_table_ddl(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Generating DDL for table {}.{}", database_name, table_name);
+        counter!("calls.get_table_ddl").increment(1);
+
+        let response = self
+            .get_table("get_table_ddl", &database_name, &table_name, catalog_id)
+            .await?;
+
+        let table = response.table().ok_or_else(|| {
+            GlueMcpError::not_found(
+                format!("Table '{database_name}.{table_name}' does not exist"),
+                json!({"database_name": database_name, "table_name": table_name}),
+            )
+            .into_mcp("get_table_ddl")
+        })?;
+
+        let ddl = ddl::create_table_ddl(table.database_name().unwrap_or(&database_name), table);
+
+        Ok(CallToolResult::success(vec![Content::text(ddl)]))
+    }
+
+    #[tool(
+        description = "Get a JSON Schema document describing the records of a table in an AWS Glue Data Catalog"
+    )]
+    async fn get_table_json_schema(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Generating JSON Schema for table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_table_json_schema").increment(1);
+
+        let response = self
+            .get_table(
+                "get_table_json_schema",
+                &database_name,
+                &table_name,
+                catalog_id,
+            )
+            .await?;
+
+        let table = response.table();
+        let columns = table
+            .and_then(|table| table.storage_descriptor())
+            .map(|sd| sd.columns())
+            .unwrap_or_default();
+
+        let schema = json_schema::table_json_schema(
+            table.map_or(&table_name, |table| table.name()),
+            columns,
+        );
+
+        Ok(CallToolResult::success(vec![Content::json(schema)?]))
+    }
+
+    #[tool(
+        description = "List the crawlers that populate an AWS Glue Data Catalog, including their state and last crawl status"
+    )]
+    async fn list_crawlers(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing crawlers");
+        counter!("calls.list_crawlers").increment(1);
+
+        let crawlers = paginate(|next_token| {
+            let request = GetCrawlersInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_crawlers", || {
+                    self.glue().get_crawlers(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let crawlers = response
+                        .crawlers()
+                        .iter()
+                        .map(|crawler| CrawlerSummary {
+                            name: crawler.name().unwrap_or_default().into(),
+                            state: crawler.state().map(|state| state.as_str().into()),
+                            last_crawl_status: crawler
+                                .last_crawl()
+                                .and_then(|last_crawl| last_crawl.status())
+                                .map(|status| status.as_str().into()),
+                        })
+                        .collect::<Vec<CrawlerSummary>>();
+                    (crawlers, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list crawlers", &e).into_mcp("list_crawlers")
+        })?;
+
+        let result = ListCrawlersResult { crawlers };
+
+        record_result_size("list_crawlers", result.crawlers.len());
+        self.list_result("list_crawlers", &result, "crawlers")
+    }
+
+    #[tool(
+        description = "Get the details of a single AWS Glue crawler, including its targets, schedule, target database and schema change policy"
+    )]
+    async fn get_crawler(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The crawler name")]
+        name: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting crawler {}", name);
+        counter!("calls.get_crawler").increment(1);
+
+        let not_found = || {
+            GlueMcpError::not_found(
+                format!("Crawler '{name}' does not exist"),
+                json!({"name": name}),
+            )
+        };
+
+        let request = GetCrawlerInput::builder().name(name.clone());
+        let response = self
+            .send_with_retry("get_crawler", || self.glue().get_crawler(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetCrawlerError::EntityNotFoundException(_)) => not_found(),
+                _ => GlueMcpError::from_aws("Failed to get crawler", &e),
+            })
+            .map_err(|e| e.into_mcp("get_crawler"))?;
+
+        let crawler = response
+            .crawler()
+            .ok_or_else(|| not_found().into_mcp("get_crawler"))?;
+
+        let targets = crawler.targets();
+        let result = CrawlerDetails {
+            name: crawler.name().unwrap_or_default().into(),
+            description: crawler.description().map(Into::into),
+            role: crawler.role().map(Into::into),
+            state: crawler.state().map(|state| state.as_str().into()),
+            database_name: crawler.database_name().map(Into::into),
+            table_prefix: crawler.table_prefix().map(Into::into),
+            schedule: crawler
+                .schedule()
+                .and_then(|schedule| schedule.schedule_expression())
+                .map(Into::into),
+            s3_targets: targets
+                .map(|targets| targets.s3_targets())
+                .unwrap_or_default()
+                .iter()
+                .map(|target| S3TargetDetails {
+                    path: target.path().map(Into::into),
+                    exclusions: target.exclusions().to_vec(),
+                })
+                .collect(),
+            catalog_targets: targets
+                .map(|targets| targets.catalog_targets())
+                .unwrap_or_default()
+                .iter()
+                .map(|target| CatalogTargetDetails {
+                    database_name: target.database_name().into(),
+                    tables: target.tables().to_vec(),
+                })
+                .collect(),
+            schema_change_policy: crawler.schema_change_policy().map(|policy| {
+                SchemaChangePolicyDetails {
+                    update_behavior: policy.update_behavior().map(|b| b.as_str().into()),
+                    delete_behavior: policy.delete_behavior().map(|b| b.as_str().into()),
+                }
+            }),
+            last_crawl_status: crawler
+                .last_crawl()
+                .and_then(|last_crawl| last_crawl.status())
+                .map(|status| status.as_str().into()),
+            last_crawl_error: crawler
+                .last_crawl()
+                .and_then(|last_crawl| last_crawl.error_message())
+                .map(Into::into),
+        };
+
+        json_result("get_crawler", &result)
+    }
+
+    #[tool(
+        description = "Start an AWS Glue crawler to refresh the metadata it populates. Requires mutations to be enabled on the server"
+    )]
+    async fn start_crawler(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The crawler name")]
+        name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "Check that the crawler exists and isn't already running without starting it, defaulting to false"
+        )]
+        dry_run: Option<bool>,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.start_crawler").increment(1);
+        self.ensure_mutations_allowed("start_crawler")?;
+
+        let already_running = || {
+            GlueMcpError::invalid_request(
+                format!("Crawler '{name}' is already running"),
+                Some(json!({"name": name, "reason": "already_running"})),
+            )
+        };
+
+        if dry_run.unwrap_or(false) {
+            log::info!("Checking whether crawler {} can be started", name);
+            let request = GetCrawlerInput::builder().name(name.clone());
+            let response = self
+                .send_with_retry("start_crawler", || self.glue().get_crawler(request.clone()))
+                .await
+                .map_err(|e| match e.as_service_error() {
+                    Some(GetCrawlerError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                        format!("Crawler '{name}' does not exist"),
+                        json!({"name": name}),
+                    ),
+                    _ => GlueMcpError::from_aws("Failed to get crawler", &e),
+                })
+                .map_err(|e| e.into_mcp("start_crawler"))?;
+            if let Some(CrawlerState::Running | CrawlerState::Stopping) =
+                response.crawler().and_then(|crawler| crawler.state())
+            {
+                return Err(already_running().into_mcp("start_crawler"));
+            }
+
+            let result = StartCrawlerResult {
+                message: format!("Crawler '{}' exists and would be started", name),
+                name,
+                dry_run: true,
+            };
+            return json_result("start_crawler", &result);
+        }
+
+        log::info!("Starting crawler {}", name);
+
+        let request = StartCrawlerInput::builder().name(name.clone());
+        self.send_mutation("start_crawler", || {
+            self.glue().start_crawler(request.clone())
+        })
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(StartCrawlerError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                format!("Crawler '{name}' does not exist"),
+                json!({"name": name}),
+            ),
+            Some(StartCrawlerError::CrawlerRunningException(_)) => already_running(),
+            _ => GlueMcpError::from_aws("Failed to start crawler", &e),
+        })
+        .map_err(|e| e.into_mcp("start_crawler"))?;
+
+        let result = StartCrawlerResult {
+            message: format!("Crawler '{}' started", name),
+            name,
+            dry_run: false,
+        };
+
+        json_result("start_crawler", &result)
+    }
+
+    #[tool(
+        description = "List the AWS Glue ETL jobs in the account, including their IAM role, command type and Glue version"
+    )]
+    async fn list_jobs(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing jobs");
+        counter!("calls.list_jobs").increment(1);
+
+        let jobs = paginate(|next_token| {
+            let request = GetJobsInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_jobs", || self.glue().get_jobs(request.clone()))
+                    .await
+                    .map(|response| {
+                        let jobs = response
+                            .jobs()
+                            .iter()
+                            .map(|job| JobSummary {
+                                name: job.name().unwrap_or_default().into(),
+                                role: job.role().map(Into::into),
+                                command_name: job
+                                    .command()
+                                    .and_then(|command| command.name())
+                                    .map(Into::into),
+                                glue_version: job.glue_version().map(Into::into),
+                            })
+                            .collect::<Vec<JobSummary>>();
+                        (jobs, response.next_token().map(Into::into))
+                    })
+            }
+        })
+        .await
+        .map_err(|e| GlueMcpError::from_aws("Failed to list jobs", &e).into_mcp("list_jobs"))?;
+
+        let result = ListJobsResult { jobs };
+
+        record_result_size("list_jobs", result.jobs.len());
+        self.list_result("list_jobs", &result, "jobs")
+    }
+
+    #[tool(
+        description = "Get the full definition of an AWS Glue ETL job, including its script location, default arguments with secrets redacted, worker configuration, timeout and connections"
+    )]
+    async fn get_job(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The job name")]
+        job_name: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting job {}", job_name);
+        counter!("calls.get_job").increment(1);
+
+        let request = GetJobInput::builder().job_name(job_name.clone());
+        let response = self
+            .send_with_retry("get_job", || self.glue().get_job(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetJobError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                    format!("Job '{job_name}' does not exist"),
+                    json!({"job_name": job_name}),
+                ),
+                _ => GlueMcpError::from_aws("Failed to get job", &e),
+            })
+            .map_err(|e| e.into_mcp("get_job"))?;
+
+        let job = response.job().ok_or_else(|| {
+            GlueMcpError::not_found(
+                format!("Job '{job_name}' does not exist"),
+                json!({"job_name": job_name}),
+            )
+            .into_mcp("get_job")
+        })?;
+
+        let command = job.command();
+        let result = JobDefinition {
+            name: job.name().unwrap_or_default().into(),
+            description: job.description().map(Into::into),
+            role: job.role().map(Into::into),
+            command_name: command.and_then(|command| command.name()).map(Into::into),
+            script_location: command
+                .and_then(|command| command.script_location())
+                .map(Into::into),
+            python_version: command
+                .and_then(|command| command.python_version())
+                .map(Into::into),
+            glue_version: job.glue_version().map(Into::into),
+            default_arguments: redact_secrets(
+                job.default_arguments()
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            ),
+            worker_type: job
+                .worker_type()
+                .map(|worker_type| worker_type.as_str().into()),
+            number_of_workers: job.number_of_workers(),
+            timeout_minutes: job.timeout(),
+            max_retries: job.max_retries(),
+            connections: job
+                .connections()
+                .map(|connections| connections.connections().to_vec())
+                .unwrap_or_default(),
+            created_on: job.created_on().and_then(rfc3339),
+            last_modified_on: job.last_modified_on().and_then(rfc3339),
+        };
+
+        json_result("get_job", &result)
+    }
+
+    #[tool(
+        description = "Get the most recent runs of an AWS Glue job, including their state, timing and any error message"
+    )]
+    async fn get_job_runs(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The job name")]
+        job_name: String,
+        #[tool(param)]
+        #[schemars(description = "The maximum number of runs to return, defaulting to 20")]
+        max_results: Option<i32>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting runs for job {}", job_name);
+        counter!("calls.get_job_runs").increment(1);
+
+        let max_results = max_results.unwrap_or(DEFAULT_JOB_RUNS);
+        if max_results <= 0 {
+            return Err(GlueMcpError::invalid_params(
+                "max_results must be greater than zero",
+                json!({"max_results": max_results}),
+            )
+            .into_mcp("get_job_runs"));
+        }
+
+        let job_runs = paginate_up_to(max_results as usize, |next_token| {
+            let request = GetJobRunsInput::builder()
+                .job_name(job_name.clone())
+                .max_results(max_results.min(JOB_RUNS_PAGE_LIMIT))
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_job_runs", || self.glue().get_job_runs(request.clone()))
+                    .await
+                    .map(|response| {
+                        let runs = response
+                            .job_runs()
+                            .iter()
+                            .map(JobRunSummary::from)
+                            .collect::<Vec<JobRunSummary>>();
+                        (runs, response.next_token().map(Into::into))
+                    })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to get job runs", &e).into_mcp("get_job_runs")
+        })?;
+
+        let result = GetJobRunsResult { job_name, job_runs };
+
+        record_result_size("get_job_runs", result.job_runs.len());
+        self.list_result("get_job_runs", &result, "job_runs")
+    }
+
+    #[tool(
+        description = "Start a run of an AWS Glue job with optional job arguments, returning the new run ID. Requires mutations to be enabled on the server"
+    )]
+    async fn start_job_run(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The job name")]
+        job_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "Job arguments for this run, overriding the job's defaults. Keys must start with '--', e.g. {\"--input_path\": \"s3://bucket/prefix\"}"
+        )]
+        arguments: Option<HashMap<String, String>>,
+        #[tool(param)]
+        #[schemars(
+            description = "Validate the arguments and check that the job exists without starting a run, defaulting to false"
+        )]
+        dry_run: Option<bool>,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.start_job_run").increment(1);
+        self.ensure_mutations_allowed("start_job_run")?;
+
+        let invalid_keys = arguments
+            .iter()
+            .flat_map(|arguments| arguments.keys())
+            .filter(|key| !key.starts_with("--"))
+            .collect::<Vec<&String>>();
+        if !invalid_keys.is_empty() {
+            return Err(GlueMcpError::invalid_params(
+                "Glue job argument names must start with '--', e.g. '--input_path'",
+                json!({"invalid_keys": invalid_keys}),
+            )
+            .into_mcp("start_job_run"));
+        }
+
+        let not_found = || {
+            GlueMcpError::not_found(
+                format!("Job '{job_name}' does not exist"),
+                json!({"job_name": job_name}),
+            )
+        };
+
+        if dry_run.unwrap_or(false) {
+            log::info!("Checking whether job {} can be started", job_name);
+            let request = GetJobInput::builder().job_name(job_name.clone());
+            self.send_with_retry("start_job_run", || self.glue().get_job(request.clone()))
+                .await
+                .map_err(|e| match e.as_service_error() {
+                    Some(GetJobError::EntityNotFoundException(_)) => not_found(),
+                    _ => GlueMcpError::from_aws("Failed to get job", &e),
+                })
+                .map_err(|e| e.into_mcp("start_job_run"))?;
+
+            let result = StartJobRunResult {
+                message: format!(
+                    "Job '{}' exists and a run would be started with {} argument overrides",
+                    job_name,
+                    arguments.as_ref().map_or(0, HashMap::len)
+                ),
+                job_name,
+                job_run_id: None,
+                dry_run: true,
+            };
+            return json_result("start_job_run", &result);
+        }
+
+        log::info!("Starting run of job {}", job_name);
+
+        let request = StartJobRunInput::builder()
+            .job_name(job_name.clone())
+            .set_arguments(arguments);
+        let response = self
+            .send_mutation("start_job_run", || {
+                self.glue().start_job_run(request.clone())
+            })
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(StartJobRunError::EntityNotFoundException(_)) => not_found(),
+                _ => GlueMcpError::from_aws("Failed to start job run", &e),
+            })
+            .map_err(|e| e.into_mcp("start_job_run"))?;
+
+        let job_run_id = response.job_run_id().unwrap_or_default();
+        let result = StartJobRunResult {
+            message: format!("Started run {} of job '{}'", job_run_id, job_name),
+            job_run_id: Some(job_run_id.into()),
+            job_name,
+            dry_run: false,
+        };
+
+        json_result("start_job_run", &result)
+    }
+
+    #[tool(
+        description = "Get the current state of a single AWS Glue job run, including its timing and any error message"
+    )]
+    async fn get_job_run(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The job name")]
+        job_name: String,
+        #[tool(param)]
+        #[schemars(description = "The job run ID, as returned by start_job_run or get_job_runs")]
+        run_id: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting run {} of job {}", run_id, job_name);
+        counter!("calls.get_job_run").increment(1);
+
+        let not_found = || {
+            GlueMcpError::not_found(
+                format!("Job run '{run_id}' of job '{job_name}' does not exist"),
+                json!({"job_name": job_name, "run_id": run_id}),
+            )
+        };
+
+        let request = GetJobRunInput::builder()
+            .job_name(job_name.clone())
+            .run_id(run_id.clone());
+        let response = self
+            .send_with_retry("get_job_run", || self.glue().get_job_run(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetJobRunError::EntityNotFoundException(_)) => not_found(),
+                _ => GlueMcpError::from_aws("Failed to get job run", &e),
+            })
+            .map_err(|e| e.into_mcp("get_job_run"))?;
+
+        let job_run = response
+            .job_run()
+            .ok_or_else(|| not_found().into_mcp("get_job_run"))?;
+
+        let result = GetJobRunResult {
+            job_run: JobRunSummary::from(job_run),
+            job_name,
+        };
+
+        json_result("get_job_run", &result)
+    }
+
+    #[tool(
+        description = "List the AWS Glue connections in the account, including their type and connection properties with secrets redacted"
+    )]
+    async fn list_connections(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Listing connections");
+        counter!("calls.list_connections").increment(1);
+
+        let connections = paginate(|next_token| {
+            let request = GetConnectionsInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .hide_password(true)
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_connections", || {
+                    self.glue().get_connections(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let connections = response
+                        .connection_list()
+                        .iter()
+                        .map(|connection| ConnectionSummary {
+                            name: connection.name().unwrap_or_default().into(),
+                            connection_type: connection
+                                .connection_type()
+                                .map(|connection_type| connection_type.as_str().into()),
+                            properties: redact_secrets(
+                                connection
+                                    .connection_properties()
+                                    .into_iter()
+                                    .flatten()
+                                    .map(|(key, value)| (key.as_str(), value.as_str())),
+                            ),
+                        })
+                        .collect::<Vec<ConnectionSummary>>();
+                    (connections, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list connections", &e).into_mcp("list_connections")
+        })?;
+
+        let result = ListConnectionsResult { connections };
+
+        record_result_size("list_connections", result.connections.len());
+        self.list_result("list_connections", &result, "connections")
+    }
+
+    #[tool(
+        description = "Get the schema history of a table in an AWS Glue Data Catalog, listing each table version's columns, newest first"
+    )]
+    async fn get_table_versions(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting versions for table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_table_versions").increment(1);
+
+        let mut versions = paginate(|next_token| {
+            let request = GetTableVersionsInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .table_name(table_name.clone())
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_table_versions", || {
+                    self.glue().get_table_versions(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let versions = response
+                        .table_versions()
+                        .iter()
+                        .map(|version| {
+                            let table = version.table();
+                            TableVersionSummary {
+                                version_id: version.version_id().unwrap_or_default().into(),
+                                update_time: table
+                                    .and_then(|table| table.update_time())
+                                    .and_then(rfc3339),
+                                columns: table
+                                    .and_then(|table| table.storage_descriptor())
+                                    .map(|sd| sd.columns())
+                                    .unwrap_or_default()
+                                    .iter()
+                                    .map(ColumnMetadata::from)
+                                    .collect(),
+                            }
+                        })
+                        .collect::<Vec<TableVersionSummary>>();
+                    (versions, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get table versions",
+                &format!("Table '{database_name}.{table_name}'"),
+                json!({"database_name": database_name, "table_name": table_name}),
+                &e,
+            )
+            .into_mcp("get_table_versions")
+        })?;
+
+        // Version IDs are increasing integers, so sort numerically rather than lexically
+        versions.sort_by_key(|version| {
+            std::cmp::Reverse(version.version_id.parse::<u64>().unwrap_or_default())
+        });
+
+        let result = GetTableVersionsResult {
+            database_name,
+            table_name,
+            versions,
+        };
+
+        record_result_size("get_table_versions", result.versions.len());
+        self.list_result("get_table_versions", &result, "versions")
+    }
+
+    #[tool(
+        description = "Get the computed column statistics (null counts, distinct counts, min/max, lengths) for columns of a table in an AWS Glue Data Catalog. Columns whose statistics Glue failed to read are listed under failed, with the error"
+    )]
+    async fn get_column_statistics(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(description = "The names of the columns to get statistics for")]
+        column_names: Vec<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting statistics for {} columns of table {}.{}",
+            column_names.len(),
+            database_name,
+            table_name
+        );
+        counter!("calls.get_column_statistics").increment(1);
+
+        // Each column is requested once, in the order first given
+        let mut column_names = column_names;
+        let mut requested = HashSet::new();
+        column_names.retain(|name| requested.insert(name.clone()));
+
+        let mut statistics = HashMap::new();
+        let mut failed = Vec::new();
+        for batch in column_names.chunks(COLUMN_STATISTICS_BATCH_LIMIT) {
+            let request = GetColumnStatisticsForTableInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .table_name(table_name.clone())
+                .set_column_names(Some(batch.to_vec()));
+            let response = self
+                .send_with_retry("get_column_statistics", || {
+                    self.glue().get_column_statistics_for_table(request.clone())
+                })
+                .await
+                .map_err(|e| {
+                    GlueMcpError::from_aws_or_not_found(
+                        "Failed to get column statistics",
+                        &format!("Table '{database_name}.{table_name}'"),
+                        json!({"database_name": database_name, "table_name": table_name}),
+                        &e,
+                    )
+                    .into_mcp("get_column_statistics")
+                })?;
+
+            for stats in response.column_statistics_list() {
+                statistics.insert(
+                    stats.column_name().to_string(),
+                    ColumnStatisticsEntry {
+                        column_name: stats.column_name().into(),
+                        column_type: Some(stats.column_type().into()),
+                        analyzed_time: rfc3339(stats.analyzed_time()),
+                        stats: stats
+                            .statistics_data()
+                            .and_then(ColumnStatisticsSummary::from_glue),
+                    },
+                );
+            }
+            failed.extend(response.errors().iter().map(|error| {
+                ColumnStatisticsFailure {
+                    column_name: error.column_name().unwrap_or_default().into(),
+                    error_code: error
+                        .error()
+                        .and_then(|detail| detail.error_code())
+                        .map(Into::into),
+                    error_message: error
+                        .error()
+                        .and_then(|detail| detail.error_message())
+                        .map(Into::into),
+                }
+            }));
+        }
+
+        // Columns without computed statistics are still reported, with null stats, unless Glue
+        // failed to read them
+        let columns = column_names
+            .iter()
+            .filter(|name| !failed.iter().any(|failure| &failure.column_name == *name))
+            .map(|name| {
+                statistics
+                    .remove(name)
+                    .unwrap_or_else(|| ColumnStatisticsEntry {
+                        column_name: name.clone(),
+                        column_type: None,
+                        analyzed_time: None,
+                        stats: None,
+                    })
+            })
+            .collect::<Vec<ColumnStatisticsEntry>>();
+
+        let result = GetColumnStatisticsResult {
+            database_name,
+            table_name,
+            columns,
+            failed,
+        };
+
+        record_result_size("get_column_statistics", result.columns.len());
+        self.list_result("get_column_statistics", &result, "columns")
+    }
+
+    #[tool(
+        description = "List the partition indexes of a table in an AWS Glue Data Catalog, including each index's key columns and status (ACTIVE, CREATING, DELETING or FAILED)"
+    )]
+    async fn get_partition_indexes(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting partition indexes for table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_partition_indexes").increment(1);
+
+        let partition_indexes = paginate(|next_token| {
+            let request = GetPartitionIndexesInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .table_name(table_name.clone())
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_partition_indexes", || {
+                    self.glue().get_partition_indexes(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let indexes = response
+                        .partition_index_descriptor_list()
+                        .iter()
+                        .map(|index| PartitionIndexSummary {
+                            name: index.index_name().into(),
+                            keys: index.keys().iter().map(|key| key.name().into()).collect(),
+                            status: index.index_status().as_str().into(),
+                        })
+                        .collect::<Vec<PartitionIndexSummary>>();
+                    (indexes, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get partition indexes",
+                &format!("Table '{database_name}.{table_name}'"),
+                json!({"database_name": database_name, "table_name": table_name}),
+                &e,
+            )
+            .into_mcp("get_partition_indexes")
+        })?;
+
+        let result = GetPartitionIndexesResult {
+            database_name,
+            table_name,
+            partition_indexes,
+        };
+
+        record_result_size("get_partition_indexes", result.partition_indexes.len());
+        self.list_result("get_partition_indexes", &result, "partition_indexes")
+    }
+
+    #[tool(
+        description = "Get the metadata of several tables in an AWS Glue database in one call, including each table's columns, partition keys and storage. Tables that can't be described get an error entry instead"
+    )]
+    async fn get_tables_metadata(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The names of the tables to describe")]
+        table_names: Vec<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting metadata for {} tables in database {}",
+            table_names.len(),
+            database_name
+        );
+        counter!("calls.get_tables_metadata").increment(1);
+
+        let tables = self
+            .fetch_tables_metadata(
+                "get_tables_metadata",
+                &database_name,
+                table_names.clone(),
+                catalog_id,
+            )
+            .await
+            .into_iter()
+            .zip(table_names)
+            .map(|(table, table_name)| match table {
+                Ok(table) => TableMetadataEntry::Table(table),
+                Err(e) => TableMetadataEntry::Error {
+                    table_name,
+                    error: e.message.into(),
+                },
+            })
+            .collect();
+
+        let result = GetTablesMetadataResult {
+            database_name,
+            tables,
+        };
+
+        record_result_size("get_tables_metadata", result.tables.len());
+        self.list_result("get_tables_metadata", &result, "tables")
+    }
+
+    #[tool(
+        description = "List the registries in the AWS Glue Schema Registry, including each registry's ARN, description and status"
+    )]
+    async fn list_registries(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing schema registries");
+        counter!("calls.list_registries").increment(1);
+
+        let registries = paginate(|next_token| {
+            let request = ListRegistriesInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_registries", || {
+                    self.glue().list_registries(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let registries = response
+                        .registries()
+                        .iter()
+                        .map(|registry| RegistrySummary {
+                            name: registry.registry_name().unwrap_or_default().into(),
+                            arn: registry.registry_arn().map(Into::into),
+                            description: registry.description().map(Into::into),
+                            status: registry.status().map(|status| status.as_str().into()),
+                        })
+                        .collect::<Vec<RegistrySummary>>();
+                    (registries, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list registries", &e).into_mcp("list_registries")
+        })?;
+
+        let result = ListRegistriesResult { registries };
+
+        record_result_size("list_registries", result.registries.len());
+        self.list_result("list_registries", &result, "registries")
+    }
+
+    #[tool(
+        description = "Get a version of a schema in the AWS Glue Schema Registry, including its Avro, JSON or Protobuf definition, defaulting to the latest version"
+    )]
+    async fn get_schema_version(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The name of the registry holding the schema")]
+        registry_name: String,
+        #[tool(param)]
+        #[schemars(description = "The schema name")]
+        schema_name: String,
+        #[tool(param)]
+        #[schemars(description = "The schema version number, defaulting to the latest version")]
+        version: Option<i64>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting version {} of schema {} in registry {}",
+            version.map_or("latest".to_string(), |version| version.to_string()),
+            schema_name,
+            registry_name
+        );
+        counter!("calls.get_schema_version").increment(1);
+
+        if let Some(version) = version.filter(|version| *version <= 0) {
+            return Err(GlueMcpError::invalid_params(
+                "version must be greater than zero",
+                json!({"version": version}),
+            )
+            .into_mcp("get_schema_version"));
+        }
+
+        let schema_version_number = match version {
+            Some(version) => SchemaVersionNumber::builder().version_number(version),
+            None => SchemaVersionNumber::builder().latest_version(true),
+        };
+        let request = GetSchemaVersionInput::builder()
+            .schema_id(
+                SchemaId::builder()
+                    .registry_name(registry_name.clone())
+                    .schema_name(schema_name.clone())
+                    .build(),
+            )
+            .schema_version_number(schema_version_number.build());
+        let response = self
+            .send_with_retry("get_schema_version", || self.glue().get_schema_version(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetSchemaVersionError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                    format!(
+                        "Schema '{schema_name}' in registry '{registry_name}' or the requested version does not exist"
+                    ),
+                    json!({
+                        "registry_name": registry_name,
+                        "schema_name": schema_name,
+                        "version": version,
+                    }),
+                ),
+                _ => GlueMcpError::from_aws("Failed to get schema version", &e),
+            })
+            .map_err(|e| e.into_mcp("get_schema_version"))?;
+
+        let result = SchemaVersionDetails {
+            registry_name,
+            schema_name,
+            version_number: response.version_number(),
+            data_format: response
+                .data_format()
+                .map(|data_format| data_format.as_str().into()),
+            status: response.status().map(|status| status.as_str().into()),
+            schema_definition: response.schema_definition().map(Into::into),
+        };
+
+        json_result("get_schema_version", &result)
+    }
+
+    #[tool(
+        description = "Get the tags of an AWS Glue resource, such as a database, table, crawler or job, given its ARN"
+    )]
+    async fn get_tags(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The ARN of the resource, e.g. arn:aws:glue:us-east-1:123456789012:database/sales"
+        )]
+        resource_arn: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting tags for {}", resource_arn);
+        counter!("calls.get_tags").increment(1);
+
+        // Addressed by ARN rather than database_name, so not checked before the call is dispatched
+        if let Some(database_name) = access::database_in_arn(&resource_arn) {
+            self.ensure_database_allowed("get_tags", database_name)?;
+        }
+
+        let request = GetTagsInput::builder().resource_arn(resource_arn.clone());
+        let response = self
+            .send_with_retry("get_tags", || self.glue().get_tags(request.clone()))
+            .await
+            .map_err(|e| {
+                GlueMcpError::from_aws_or_not_found(
+                    "Failed to get tags",
+                    &format!("Resource '{resource_arn}'"),
+                    json!({"resource_arn": resource_arn}),
+                    &e,
+                )
+                .into_mcp("get_tags")
+            })?;
+
+        let result = GetTagsResult {
+            resource_arn,
+            tags: response.tags().cloned().unwrap_or_default(),
+        };
+
+        json_result("get_tags", &result)
+    }
+
+    #[tool(
+        description = "Get the encryption settings of an AWS Glue Data Catalog, including whether metadata is encrypted at rest, the KMS keys used, and whether connection passwords are encrypted"
+    )]
+    async fn get_data_catalog_encryption_settings(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting Data Catalog encryption settings");
+        counter!("calls.get_data_catalog_encryption_settings").increment(1);
+
+        let request = GetDataCatalogEncryptionSettingsInput::builder().set_catalog_id(catalog_id);
+        let response = self
+            .send_with_retry("get_data_catalog_encryption_settings", || {
+                self.glue()
+                    .get_data_catalog_encryption_settings(request.clone())
+            })
+            .await
+            .map_err(|e| {
+                GlueMcpError::from_aws("Failed to get Data Catalog encryption settings", &e)
+                    .into_mcp("get_data_catalog_encryption_settings")
+            })?;
+
+        let settings = response.data_catalog_encryption_settings();
+        let encryption_at_rest = settings.and_then(|settings| settings.encryption_at_rest());
+        let password_encryption =
+            settings.and_then(|settings| settings.connection_password_encryption());
+        let result = EncryptionSettings {
+            catalog_encryption_mode: encryption_at_rest
+                .map(|encryption| encryption.catalog_encryption_mode().as_str().into()),
+            catalog_kms_key_id: encryption_at_rest
+                .and_then(|encryption| encryption.sse_aws_kms_key_id())
+                .map(Into::into),
+            connection_password_encrypted: password_encryption
+                .is_some_and(|encryption| encryption.return_connection_password_encrypted()),
+            connection_password_kms_key_id: password_encryption
+                .and_then(|encryption| encryption.aws_kms_key_id())
+                .map(Into::into),
+        };
+
+        json_result("get_data_catalog_encryption_settings", &result)
+    }
+
+    #[tool(
+        description = "Get the resource policy controlling access to an AWS Glue Data Catalog, including the policy JSON and its hash"
+    )]
+    async fn get_resource_policy(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting Data Catalog resource policy");
+        counter!("calls.get_resource_policy").increment(1);
+
+        // GetResourcePolicy addresses catalogs by ARN rather than ID, defaulting to the caller's
+        let resource_arn = match catalog_id {
+            Some(catalog_id) => {
+                let region = self.glue().region().ok_or_else(|| {
+                    GlueMcpError::invalid_request(
+                        "No AWS region is configured, so the catalog's ARN can't be built",
+                        Some(json!({"catalog_id": catalog_id})),
+                    )
+                    .into_mcp("get_resource_policy")
+                })?;
+                Some(catalog_arn(&region, &catalog_id))
+            }
+            None => None,
+        };
+        let request = GetResourcePolicyInput::builder().set_resource_arn(resource_arn);
+        let response = self
+            .send_with_retry("get_resource_policy", || {
+                self.glue().get_resource_policy(request.clone())
+            })
+            .await;
+
+        let result = match response {
+            Ok(response) => ResourcePolicyDetails {
+                policy: response.policy_in_json().map(Into::into),
+                policy_hash: response.policy_hash().map(Into::into),
+                create_time: response.create_time().and_then(rfc3339),
+                update_time: response.update_time().and_then(rfc3339),
+                message: None,
+            },
+            Err(e) if error::is_not_found(&e) => ResourcePolicyDetails {
+                policy: None,
+                policy_hash: None,
+                create_time: None,
+                update_time: None,
+                message: Some("No resource policy is configured for this catalog".into()),
+            },
+            Err(e) => {
+                return Err(GlueMcpError::from_aws("Failed to get resource policy", &e)
+                    .into_mcp("get_resource_policy"));
+            }
+        };
+
+        json_result("get_resource_policy", &result)
+    }
+
+    #[tool(
+        description = "Summarize the S3 data under a table's location in an AWS Glue Data Catalog, returning the number of objects and their total size. Useful for estimating the cost of scanning the table"
+    )]
+    async fn get_table_storage_summary(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The maximum number of S3 objects to list before stopping, defaulting to 100000"
+        )]
+        max_objects: Option<i32>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Summarizing storage of table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_table_storage_summary").increment(1);
+
+        let max_objects = match max_objects {
+            Some(max) if max <= 0 => {
+                return Err(GlueMcpError::invalid_params(
+                    "max_objects must be greater than zero",
+                    json!({"max_objects": max}),
+                )
+                .into_mcp("get_table_storage_summary"));
+            }
+            Some(max) => max as usize,
+            None => STORAGE_SUMMARY_MAX_OBJECTS,
+        };
+
+        let s3 = self.s3_client().ok_or_else(|| {
+            GlueMcpError::invalid_request(
+                "This server was created without an S3 client, so it can't read table storage",
+                None,
+            )
+            .into_mcp("get_table_storage_summary")
+        })?;
+
+        let response = self
+            .get_table(
+                "get_table_storage_summary",
+                &database_name,
+                &table_name,
+                catalog_id,
+            )
+            .await?;
+        let location = response
+            .table()
+            .and_then(|table| table.storage_descriptor())
+            .and_then(|sd| sd.location())
+            .unwrap_or_default()
+            .to_string();
+        let (bucket, prefix) = parse_s3_location(&location).ok_or_else(|| {
+            GlueMcpError::invalid_request(
+                format!("Table '{database_name}.{table_name}' isn't stored in S3"),
+                Some(json!({"location": location})),
+            )
+            .into_mcp("get_table_storage_summary")
+        })?;
+
+        let Paginated {
+            items: sizes,
+            truncated,
+            ..
+        } = paginate_limited(max_objects, |continuation_token| {
+            let request = s3
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix.clone())
+                .set_continuation_token(continuation_token);
+            async move {
+                self.send_with_retry("get_table_storage_summary", || request.clone().send())
+                    .await
+                    .map(|response| {
+                        let sizes = response
+                            .contents()
+                            .iter()
+                            .map(|object| object.size().unwrap_or_default())
+                            .collect::<Vec<i64>>();
+                        (sizes, response.next_continuation_token().map(Into::into))
+                    })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list table objects in S3", &e)
+                .into_mcp("get_table_storage_summary")
+        })?;
+
+        let result = TableStorageSummary {
+            database_name,
+            table_name,
+            location,
+            object_count: sizes.len(),
+            total_size_bytes: sizes.iter().sum(),
+            truncated,
+        };
+
+        json_result("get_table_storage_summary", &result)
+    }
+
+    #[tool(
+        description = "List the AWS Glue triggers in the account, including each trigger's type, state, schedule and the jobs and crawlers it starts"
+    )]
+    async fn list_triggers(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing triggers");
+        counter!("calls.list_triggers").increment(1);
+
+        let triggers = paginate(|next_token| {
+            let request = GetTriggersInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_triggers", || {
+                    self.glue().get_triggers(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let triggers = response
+                        .triggers()
+                        .iter()
+                        .map(|trigger| TriggerSummary {
+                            name: trigger.name().unwrap_or_default().into(),
+                            trigger_type: trigger
+                                .r#type()
+                                .map(|trigger_type| trigger_type.as_str().into()),
+                            state: trigger.state().map(|state| state.as_str().into()),
+                            schedule: trigger.schedule().map(Into::into),
+                            workflow_name: trigger.workflow_name().map(Into::into),
+                            actions: trigger
+                                .actions()
+                                .iter()
+                                .map(|action| TriggerAction {
+                                    job_name: action.job_name().map(Into::into),
+                                    crawler_name: action.crawler_name().map(Into::into),
+                                })
+                                .collect(),
+                        })
+                        .collect::<Vec<TriggerSummary>>();
+                    (triggers, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list triggers", &e).into_mcp("list_triggers")
+        })?;
+
+        let result = ListTriggersResult { triggers };
+
+        record_result_size("list_triggers", result.triggers.len());
+        self.list_result("list_triggers", &result, "triggers")
+    }
+
+    #[tool(
+        description = "Get an AWS Glue workflow, including its graph of trigger, job and crawler nodes and the edges between them, and the status of its last run"
+    )]
+    async fn get_workflow(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The workflow name")]
+        name: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting workflow {}", name);
+        counter!("calls.get_workflow").increment(1);
+
+        let not_found = || {
+            GlueMcpError::not_found(
+                format!("Workflow '{name}' does not exist"),
+                json!({"name": name}),
+            )
+        };
+
+        let request = GetWorkflowInput::builder()
+            .name(name.clone())
+            .include_graph(true);
+        let response = self
+            .send_with_retry("get_workflow", || self.glue().get_workflow(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetWorkflowError::EntityNotFoundException(_)) => not_found(),
+                _ => GlueMcpError::from_aws("Failed to get workflow", &e),
+            })
+            .map_err(|e| e.into_mcp("get_workflow"))?;
+
+        let workflow = response
+            .workflow()
+            .ok_or_else(|| not_found().into_mcp("get_workflow"))?;
+
+        let graph = workflow.graph();
+        let result = WorkflowDetails {
+            name: workflow.name().unwrap_or_default().into(),
+            description: workflow.description().map(Into::into),
+            nodes: graph
+                .map(|graph| graph.nodes())
+                .unwrap_or_default()
+                .iter()
+                .map(|node| WorkflowNode {
+                    id: node.unique_id().map(Into::into),
+                    node_type: node.r#type().map(|node_type| node_type.as_str().into()),
+                    name: node.name().map(Into::into),
+                })
+                .collect(),
+            edges: graph
+                .map(|graph| graph.edges())
+                .unwrap_or_default()
+                .iter()
+                .map(|edge| WorkflowEdge {
+                    source_id: edge.source_id().map(Into::into),
+                    destination_id: edge.destination_id().map(Into::into),
+                })
+                .collect(),
+            last_run: workflow.last_run().map(|run| WorkflowRunSummary {
+                status: run.status().map(|status| status.as_str().into()),
+                started_on: run.started_on().and_then(rfc3339),
+                completed_on: run.completed_on().and_then(rfc3339),
+                error_message: run.error_message().map(Into::into),
+            }),
+        };
+
+        json_result("get_workflow", &result)
+    }
+
+    #[tool(
+        description = "List the classifiers crawlers use to infer file formats, including each classifier's type (Grok, XML, JSON or Csv) and the pattern, path or delimiter it matches with"
+    )]
+    async fn list_classifiers(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing classifiers");
+        counter!("calls.list_classifiers").increment(1);
+
+        let classifiers = paginate(|next_token| {
+            let request = GetClassifiersInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_classifiers", || {
+                    self.glue().get_classifiers(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let classifiers = response
+                        .classifiers()
+                        .iter()
+                        .filter_map(classifier_summary)
+                        .collect::<Vec<ClassifierSummary>>();
+                    (classifiers, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list classifiers", &e).into_mcp("list_classifiers")
+        })?;
+
+        let result = ListClassifiersResult { classifiers };
+
+        record_result_size("list_classifiers", result.classifiers.len());
+        self.list_result("list_classifiers", &result, "classifiers")
+    }
+
+    #[tool(
+        description = "List the user-defined functions registered in an AWS Glue database, including each function's class name and the resources it loads"
+    )]
+    async fn list_user_defined_functions(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Listing user-defined functions in database {}",
+            database_name
+        );
+        counter!("calls.list_user_defined_functions").increment(1);
+
+        let functions = paginate(|next_token| {
+            let request = GetUserDefinedFunctionsInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .pattern("*")
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_user_defined_functions", || {
+                    self.glue().get_user_defined_functions(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let functions = response
+                        .user_defined_functions()
+                        .iter()
+                        .map(|function| UserDefinedFunctionSummary {
+                            name: function.function_name().unwrap_or_default().into(),
+                            class_name: function.class_name().map(Into::into),
+                            owner_name: function.owner_name().map(Into::into),
+                            resource_uris: function
+                                .resource_uris()
+                                .iter()
+                                .map(|resource| FunctionResource {
+                                    resource_type: resource
+                                        .resource_type()
+                                        .map(|resource_type| resource_type.as_str().into()),
+                                    uri: resource.uri().map(Into::into),
+                                })
+                                .collect(),
+                        })
+                        .collect::<Vec<UserDefinedFunctionSummary>>();
+                    (functions, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to list user-defined functions",
+                &format!("Database '{database_name}'"),
+                json!({"database_name": database_name}),
+                &e,
+            )
+            .into_mcp("list_user_defined_functions")
+        })?;
+
+        let result = ListUserDefinedFunctionsResult {
+            database_name,
+            functions,
+        };
+
+        record_result_size("list_user_defined_functions", result.functions.len());
+        self.list_result("list_user_defined_functions", &result, "functions")
+    }
+
+    #[tool(
+        description = "List the AWS Glue Data Quality rulesets, optionally only those targeting a given table, including each ruleset's description and number of rules"
+    )]
+    async fn list_data_quality_rulesets(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "Only return rulesets targeting a table in this database; requires table_name"
+        )]
+        database_name: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "Only return rulesets targeting this table; requires database_name"
+        )]
+        table_name: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog holding the target table, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Listing data quality rulesets");
+        counter!("calls.list_data_quality_rulesets").increment(1);
+
+        let filter = match (database_name, table_name) {
+            (None, None) => None,
+            (Some(database_name), Some(table_name)) => {
+                let target_table = DataQualityTargetTable::builder()
+                    .database_name(database_name.clone())
+                    .table_name(table_name.clone())
+                    .set_catalog_id(catalog_id)
+                    .build()
+                    .map_err(|e| {
+                        GlueMcpError::invalid_params(
+                            format!("Invalid target table: {e}"),
+                            json!({"database_name": database_name, "table_name": table_name}),
+                        )
+                        .into_mcp("list_data_quality_rulesets")
+                    })?;
+                Some(
+                    DataQualityRulesetFilterCriteria::builder()
+                        .target_table(target_table)
+                        .build(),
+                )
+            }
+            (database_name, table_name) => {
+                return Err(GlueMcpError::invalid_params(
+                    "database_name and table_name must be given together",
+                    json!({"database_name": database_name, "table_name": table_name}),
+                )
+                .into_mcp("list_data_quality_rulesets"));
+            }
+        };
+
+        let rulesets = paginate(|next_token| {
+            let request = ListDataQualityRulesetsInput::builder()
+                .set_filter(filter.clone())
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_data_quality_rulesets", || {
+                    self.glue().list_data_quality_rulesets(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let rulesets = response
+                        .rulesets()
+                        .iter()
+                        .map(|ruleset| DataQualityRulesetSummary {
+                            name: ruleset.name().unwrap_or_default().into(),
+                            description: ruleset.description().map(Into::into),
+                            rule_count: ruleset.rule_count(),
+                            database_name: ruleset
+                                .target_table()
+                                .map(|table| table.database_name().into()),
+                            table_name: ruleset
+                                .target_table()
+                                .map(|table| table.table_name().into()),
+                            created_on: ruleset.created_on().and_then(rfc3339),
+                            last_modified_on: ruleset.last_modified_on().and_then(rfc3339),
+                        })
+                        .collect::<Vec<DataQualityRulesetSummary>>();
+                    (rulesets, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list data quality rulesets", &e)
+                .into_mcp("list_data_quality_rulesets")
+        })?;
+
+        let result = ListDataQualityRulesetsResult { rulesets };
+
+        record_result_size("list_data_quality_rulesets", result.rulesets.len());
+        self.list_result("list_data_quality_rulesets", &result, "rulesets")
+    }
+
+    #[tool(
+        description = "Get an AWS Glue Data Quality evaluation result, including whether each rule passed and the metric values it observed"
+    )]
+    async fn get_data_quality_result(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The ID of the data quality result")]
+        result_id: String,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting data quality result {}", result_id);
+        counter!("calls.get_data_quality_result").increment(1);
+
+        let request = GetDataQualityResultInput::builder().result_id(result_id.clone());
+        let response = self
+            .send_with_retry("get_data_quality_result", || {
+                self.glue().get_data_quality_result(request.clone())
+            })
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(GetDataQualityResultError::EntityNotFoundException(_)) => {
+                    GlueMcpError::not_found(
+                        format!("Data quality result '{result_id}' does not exist"),
+                        json!({"result_id": result_id}),
+                    )
+                }
+                _ => GlueMcpError::from_aws("Failed to get data quality result", &e),
+            })
+            .map_err(|e| e.into_mcp("get_data_quality_result"))?;
+
+        let glue_table = response
+            .data_source()
+            .and_then(|data_source| data_source.glue_table());
+        let result = DataQualityResultDetails {
+            result_id: response.result_id().unwrap_or(&result_id).into(),
+            ruleset_name: response.ruleset_name().map(Into::into),
+            database_name: glue_table.map(|table| table.database_name().into()),
+            table_name: glue_table.map(|table| table.table_name().into()),
+            score: response.score(),
+            started_on: response.started_on().and_then(rfc3339),
+            completed_on: response.completed_on().and_then(rfc3339),
+            job_name: response.job_name().map(Into::into),
+            job_run_id: response.job_run_id().map(Into::into),
+            rule_results: response
+                .rule_results()
+                .iter()
+                .map(|rule| DataQualityRuleOutcome {
+                    name: rule.name().unwrap_or_default().into(),
+                    result: rule.result().map(|result| result.as_str().into()),
+                    evaluation_message: rule.evaluation_message().map(Into::into),
+                    evaluated_metrics: rule.evaluated_metrics().cloned().unwrap_or_default(),
+                })
+                .collect(),
+        };
+
+        json_result("get_data_quality_result", &result)
+    }
+
+    #[tool(
+        description = "List the AWS Glue interactive sessions in the account, including each session's status, creation time and IAM role"
+    )]
+    async fn list_sessions(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing interactive sessions");
+        counter!("calls.list_sessions").increment(1);
+
+        let sessions = paginate(|next_token| {
+            let request = ListSessionsInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_sessions", || {
+                    self.glue().list_sessions(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let sessions = response
+                        .sessions()
+                        .iter()
+                        .map(|session| SessionSummary {
+                            id: session.id().unwrap_or_default().into(),
+                            status: session.status().map(|status| status.as_str().into()),
+                            created_on: session.created_on().and_then(rfc3339),
+                            role: session.role().map(Into::into),
+                            description: session.description().map(Into::into),
+                        })
+                        .collect::<Vec<SessionSummary>>();
+                    (sessions, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list sessions", &e).into_mcp("list_sessions")
+        })?;
+
+        let result = ListSessionsResult { sessions };
+
+        record_result_size("list_sessions", result.sessions.len());
+        self.list_result("list_sessions", &result, "sessions")
+    }
+
+    #[tool(
+        description = "Get the status of an AWS Glue table optimizer, such as automatic compaction of an Iceberg table, including whether it's enabled, its IAM role and its last run"
+    )]
+    async fn get_table_optimizer(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The optimizer type: \"compaction\", \"retention\" or \"orphan_file_deletion\""
+        )]
+        optimizer_type: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Getting {} optimizer for table {}.{}",
+            optimizer_type,
+            database_name,
+            table_name
+        );
+        counter!("calls.get_table_optimizer").increment(1);
+
+        let optimizer_type = optimizer_type.to_lowercase();
+        let table_optimizer_type = TableOptimizerType::from(optimizer_type.as_str());
+        if let TableOptimizerType::Unknown(_) = table_optimizer_type {
+            return Err(GlueMcpError::invalid_params(
+                format!("Unknown optimizer type '{optimizer_type}'"),
+                json!({
+                    "optimizer_type": optimizer_type,
+                    "valid_types": TableOptimizerType::values(),
+                }),
+            )
+            .into_mcp("get_table_optimizer"));
+        }
+
+        // GetTableOptimizer requires the catalog ID, so take it from the table, which also checks
+        // that the table exists
+        let response = self
+            .get_table(
+                "get_table_optimizer",
+                &database_name,
+                &table_name,
+                catalog_id.clone(),
+            )
+            .await?;
+        let table = response.table();
+        let catalog_id = table
+            .and_then(|table| table.catalog_id())
+            .or(catalog_id.as_deref())
+            .ok_or_else(|| {
+                GlueMcpError::invalid_request(
+                    format!(
+                        "Glue didn't report the catalog of table '{database_name}.{table_name}'; pass catalog_id explicitly"
+                    ),
+                    Some(json!({"database_name": database_name, "table_name": table_name})),
+                )
+                .into_mcp("get_table_optimizer")
+            })?;
+        let database_name = table
+            .and_then(|table| table.database_name())
+            .unwrap_or(&database_name);
+        let table_name = table.map_or(table_name.as_str(), |table| table.name());
+
+        let request = GetTableOptimizerInput::builder()
+            .catalog_id(catalog_id)
+            .database_name(database_name)
+            .table_name(table_name)
+            .r#type(table_optimizer_type);
+        let response = self
+            .send_with_retry("get_table_optimizer", || {
+                self.glue().get_table_optimizer(request.clone())
+            })
+            .await;
+
+        let result = match response {
+            Ok(response) => {
+                let optimizer = response.table_optimizer();
+                let configuration = optimizer.and_then(|optimizer| optimizer.configuration());
+                TableOptimizerDetails {
+                    database_name: database_name.into(),
+                    table_name: table_name.into(),
+                    optimizer_type,
+                    configured: optimizer.is_some(),
+                    enabled: configuration.and_then(|configuration| configuration.enabled()),
+                    role_arn: configuration
+                        .and_then(|configuration| configuration.role_arn())
+                        .map(Into::into),
+                    last_run: optimizer
+                        .and_then(|optimizer| optimizer.last_run())
+                        .map(|run| TableOptimizerRunSummary {
+                            event_type: run.event_type().map(|event| event.as_str().into()),
+                            start_time: run.start_timestamp().and_then(rfc3339),
+                            end_time: run.end_timestamp().and_then(rfc3339),
+                            error: run.error().map(Into::into),
+                        }),
+                    message: None,
+                }
+            }
+            Err(e) if error::is_not_found(&e) => TableOptimizerDetails {
+                message: Some(format!(
+                    "No {optimizer_type} optimizer is configured for this table"
+                )),
+                database_name: database_name.into(),
+                table_name: table_name.into(),
+                optimizer_type,
+                configured: false,
+                enabled: None,
+                role_arn: None,
+                last_run: None,
+            },
+            Err(e) => {
+                return Err(GlueMcpError::from_aws("Failed to get table optimizer", &e)
+                    .into_mcp("get_table_optimizer"));
+            }
+        };
+
+        json_result("get_table_optimizer", &result)
+    }
+
+    #[tool(
+        description = "Get run metrics for AWS Glue crawlers, including their median and last runtimes, the tables their last run created, updated and deleted, and the time left in a current run"
+    )]
+    async fn get_crawler_metrics(
+        &self,
+        #[tool(param)]
+        #[schemars(
+            description = "The names of the crawlers to get metrics for, defaulting to all of them when empty"
+        )]
+        crawler_names: Vec<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!("Getting metrics for {} crawlers", crawler_names.len());
+        counter!("calls.get_crawler_metrics").increment(1);
+
+        let crawler_name_list = (!crawler_names.is_empty()).then(|| crawler_names.clone());
+        let crawlers = paginate(|next_token| {
+            let request = GetCrawlerMetricsInput::builder()
+                .set_crawler_name_list(crawler_name_list.clone())
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_crawler_metrics", || {
+                    self.glue().get_crawler_metrics(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let metrics = response
+                        .crawler_metrics_list()
+                        .iter()
+                        .map(|metrics| CrawlerMetricsSummary {
+                            crawler_name: metrics.crawler_name().unwrap_or_default().into(),
+                            median_runtime_seconds: metrics.median_runtime_seconds(),
+                            last_runtime_seconds: metrics.last_runtime_seconds(),
+                            time_left_seconds: metrics.time_left_seconds(),
+                            still_estimating: metrics.still_estimating(),
+                            tables_created: metrics.tables_created(),
+                            tables_updated: metrics.tables_updated(),
+                            tables_deleted: metrics.tables_deleted(),
+                        })
+                        .collect::<Vec<CrawlerMetricsSummary>>();
+                    (metrics, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to get crawler metrics", &e)
+                .into_mcp("get_crawler_metrics")
+        })?;
+
+        let missing = crawler_names
+            .into_iter()
+            .filter(|name| !crawlers.iter().any(|metrics| metrics.crawler_name == *name))
+            .collect();
+        let result = GetCrawlerMetricsResult { crawlers, missing };
+
+        record_result_size("get_crawler_metrics", result.crawlers.len());
+        self.list_result("get_crawler_metrics", &result, "crawlers")
+    }
+
+    #[tool(
+        description = "Stop a running AWS Glue crawler. Requires mutations to be enabled on the server"
+    )]
+    async fn stop_crawler(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The crawler name")]
+        name: String,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.stop_crawler").increment(1);
+        self.ensure_mutations_allowed("stop_crawler")?;
+        log::info!("Stopping crawler {}", name);
+
+        let request = StopCrawlerInput::builder().name(name.clone());
+        self.send_mutation("stop_crawler", || self.glue().stop_crawler(request.clone()))
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(StopCrawlerError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                    format!("Crawler '{name}' does not exist"),
+                    json!({"name": name}),
+                ),
+                Some(StopCrawlerError::CrawlerNotRunningException(_)) => {
+                    GlueMcpError::invalid_request(
+                        format!("Crawler '{name}' isn't running"),
+                        Some(json!({"name": name, "reason": "not_running"})),
+                    )
+                }
+                Some(StopCrawlerError::CrawlerStoppingException(_)) => {
+                    GlueMcpError::invalid_request(
+                        format!("Crawler '{name}' is already stopping"),
+                        Some(json!({"name": name, "reason": "already_stopping"})),
+                    )
+                }
+                _ => GlueMcpError::from_aws("Failed to stop crawler", &e),
+            })
+            .map_err(|e| e.into_mcp("stop_crawler"))?;
+
+        let result = StopCrawlerResult {
+            message: format!("Crawler '{}' is stopping", name),
+            name,
+        };
+
+        json_result("stop_crawler", &result)
+    }
+
+    #[tool(
+        description = "Stop one or more runs of an AWS Glue job, returning which runs were stopped and why any others couldn't be. Requires mutations to be enabled on the server"
+    )]
+    async fn batch_stop_job_run(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The job name")]
+        job_name: String,
+        #[tool(param)]
+        #[schemars(description = "The IDs of the runs to stop")]
+        run_ids: Vec<String>,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.batch_stop_job_run").increment(1);
+        self.ensure_mutations_allowed("batch_stop_job_run")?;
+
+        if run_ids.is_empty() {
+            return Err(GlueMcpError::invalid_params(
+                "run_ids must name at least one job run",
+                json!({"run_ids": run_ids}),
+            )
+            .into_mcp("batch_stop_job_run"));
+        }
+
+        log::info!("Stopping {} runs of job {}", run_ids.len(), job_name);
+
+        let request = BatchStopJobRunInput::builder()
+            .job_name(job_name.clone())
+            .set_job_run_ids(Some(run_ids));
+        let response = self
+            .send_mutation("batch_stop_job_run", || {
+                self.glue().batch_stop_job_run(request.clone())
+            })
+            .await
+            .map_err(|e| {
+                GlueMcpError::from_aws_or_not_found(
+                    "Failed to stop job runs",
+                    &format!("Job '{job_name}'"),
+                    json!({"job_name": job_name}),
+                    &e,
+                )
+                .into_mcp("batch_stop_job_run")
+            })?;
+
+        let result = BatchStopJobRunResult {
+            stopped: response
+                .successful_submissions()
+                .iter()
+                .filter_map(|submission| submission.job_run_id())
+                .map(Into::into)
+                .collect(),
+            failed: response
+                .errors()
+                .iter()
+                .map(|error| JobRunStopFailure {
+                    job_run_id: error.job_run_id().unwrap_or_default().into(),
+                    error_code: error
+                        .error_detail()
+                        .and_then(|detail| detail.error_code())
+                        .map(Into::into),
+                    error_message: error
+                        .error_detail()
+                        .and_then(|detail| detail.error_message())
+                        .map(Into::into),
+                })
+                .collect(),
+            job_name,
+        };
+
+        json_result("batch_stop_job_run", &result)
+    }
+
+    #[tool(
+        description = "Summarize the partitions of a table in an AWS Glue Data Catalog: how many there are and the values of the most recently created one"
+    )]
+    async fn get_table_partition_summary(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The most partitions to count before stopping, defaulting to 10000"
+        )]
+        max_partitions: Option<i32>,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        log::info!(
+            "Summarizing partitions of table {}.{}",
+            database_name,
+            table_name
+        );
+        counter!("calls.get_table_partition_summary").increment(1);
+
+        let max_partitions = match max_partitions {
+            Some(max) if max <= 0 => {
+                return Err(GlueMcpError::invalid_params(
+                    "max_partitions must be greater than zero",
+                    json!({"max_partitions": max}),
+                )
+                .into_mcp("get_table_partition_summary"));
+            }
+            Some(max) => max as usize,
+            None => PARTITION_SUMMARY_MAX_PARTITIONS,
+        };
+
+        let Paginated {
+            items: partitions,
+            truncated,
+            ..
+        } = paginate_limited(max_partitions, |next_token| {
+            let request = GetPartitionsInput::builder()
+                .set_catalog_id(catalog_id.clone())
+                .database_name(database_name.clone())
+                .table_name(table_name.clone())
+                .exclude_column_schema(true)
+                .set_next_token(next_token);
+            async move {
+                self.send_with_retry("get_table_partition_summary", || {
+                    self.glue().get_partitions(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let partitions = response
+                        .partitions()
+                        .iter()
+                        .map(|partition| {
+                            (
+                                partition.values().to_vec(),
+                                partition.creation_time().cloned(),
+                            )
+                        })
+                        .collect::<Vec<(Vec<String>, Option<DateTime>)>>();
+                    (partitions, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws_or_not_found(
+                "Failed to get partitions",
+                &format!("Table '{database_name}.{table_name}'"),
+                json!({"database_name": database_name, "table_name": table_name}),
+                &e,
+            )
+            .into_mcp("get_table_partition_summary")
+        })?;
+
+        let partition_count = partitions.len();
+        let latest = partitions
+            .into_iter()
+            .filter_map(|(values, created_on)| Some((values, created_on?)))
+            .max_by_key(|(_, created_on)| created_on.as_nanos());
+
+        let result = TablePartitionSummary {
+            database_name,
+            table_name,
+            partition_count,
+            truncated,
+            latest_partition_created_on: latest
+                .as_ref()
+                .and_then(|(_, created_on)| rfc3339(created_on)),
+            latest_partition_values: latest.map(|(values, _)| values),
+        };
+
+        json_result("get_table_partition_summary", &result)
+    }
+
+    #[tool(
+        description = "Delete a table from an AWS Glue Data Catalog. The first call returns a confirmation token describing the deletion; the table is only deleted when called again with that token. Requires mutations to be enabled on the server"
+    )]
+    async fn delete_table(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to modify, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+        #[tool(param)]
+        #[schemars(
+            description = "The token returned by a previous call for this same table, confirming the deletion"
+        )]
+        confirmation_token: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.delete_table").increment(1);
+        self.ensure_mutations_allowed("delete_table")?;
+        // Also checked for every call with a database name, but repeated so that no path can
+        // delete from a denied database
+        self.ensure_database_allowed("delete_table", &database_name)?;
+
+        let action = format!(
+            "Delete table '{}.{}' from the {} catalog",
+            database_name,
+            table_name,
+            catalog_id.as_deref().unwrap_or("default")
+        );
+        if confirmation_token.is_none() {
+            // Only issue tokens for tables that exist, so that a mistaken name fails up front
+            self.get_table(
+                "delete_table",
+                &database_name,
+                &table_name,
+                catalog_id.clone(),
+            )
+            .await?;
+        }
+        if let Some(pending) = self
+            .confirm_destructive("delete_table", action, confirmation_token)
+            .await?
+        {
+            return Ok(pending);
+        }
+
+        log::info!("Deleting table {}.{}", database_name, table_name);
+
+        let request = DeleteTableInput::builder()
+            .set_catalog_id(catalog_id)
+            .database_name(database_name.clone())
+            .name(table_name.clone());
+        self.send_mutation("delete_table", || self.glue().delete_table(request.clone()))
+            .await
+            .map_err(|e| {
+                GlueMcpError::from_aws_or_not_found(
+                    "Failed to delete table",
+                    &format!("Table '{database_name}.{table_name}'"),
+                    json!({"database_name": database_name, "table_name": table_name}),
+                    &e,
+                )
+                .into_mcp("delete_table")
+            })?;
+        if let Some(cache) = &self.cache {
+            cache::invalidate_table(cache, &database_name, &table_name);
+        }
+
+        let result = DeleteTableResult {
+            message: format!("Table '{}.{}' deleted", database_name, table_name),
+            database_name,
+            table_name,
+        };
+
+        json_result("delete_table", &result)
+    }
+
+    #[tool(
+        description = "Describe what this server can do without calling AWS: its region, whether caching, mutations and authentication are enabled, and its tools grouped by category, noting which change state in AWS"
+    )]
+    async fn describe_capabilities(&self) -> Result<CallToolResult, McpError> {
+        counter!("calls.describe_capabilities").increment(1);
+
+        let mut tools = BTreeMap::<String, Vec<ToolCapability>>::new();
+        for tool in Self::tool_box().list() {
+            let mutation = MUTATING_TOOLS.contains(&tool.name.as_ref());
+            tools
+                .entry(tool_category(&tool.name).into())
+                .or_default()
+                .push(ToolCapability {
+                    name: tool.name.to_string(),
+                    mutation,
+                    available: !mutation || self.allow_mutations,
+                });
+        }
+        for category in tools.values_mut() {
+            category.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        let result = CapabilitiesDescription {
+            server_version: env!("CARGO_PKG_VERSION").into(),
+            region: self.glue().region(),
+            catalog_id: self
+                .reconnect
+                .as_ref()
+                .and_then(|builder| builder.assumed_account_id())
+                .map(Into::into),
+            caching_enabled: self.cache.is_some(),
+            mutations_enabled: self.allow_mutations,
+            auth_required: self.auth_required,
+            case_insensitive_names: self.case_insensitive_names,
+            databases_restricted: !self.database_access.is_unrestricted(),
+            tools,
+        };
 
-    /// Creates a new GlueDataCatalog using the default AWS configuration from environment
-    #[allow(dead_code)]
-    pub async fn from_env() -> Self {
-        let config = aws_config::defaults(BehaviorVersion::latest()).load().await;
-        let client = aws_sdk_glue::Client::new(&config);
-        client
-            .get_databases()
-            .send()
-            .await
-            .expect("Couldn't connect to AWS");
-        Self { client }
+        json_result("describe_capabilities", &result)
     }
 
-    #[tool(description = "List the databases in an AWS Glue Data Catalog")]
-    async fn list_databases(&self) -> Result<CallToolResult, McpError> {
+    #[tool(
+        description = "Infer where a table in an AWS Glue Data Catalog comes from: the crawlers that target it or its S3 location, and the ETL jobs whose arguments mention it, its database or its location. The result is a best-effort guess, not recorded lineage"
+    )]
+    async fn get_table_lineage(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The database name")]
+        database_name: String,
+        #[tool(param)]
+        #[schemars(description = "The table name")]
+        table_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog to query, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
         log::info!(
-            "Listing databases in {}",
-            self.client.config().region().unwrap()
+            "Inferring lineage of table {}.{}",
+            database_name,
+            table_name
         );
-        counter!("calls.list_databases").increment(1);
+        counter!("calls.get_table_lineage").increment(1);
 
-        let response = self.client.get_databases().send().await.map_err(|e| {
-            counter!("errors.list_databases.aws_call_error").increment(1);
-            McpError::internal_error(
-                "Failed to list databases",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let response = self
+            .get_table("get_table_lineage", &database_name, &table_name, catalog_id)
+            .await?;
+        let table = response.table();
+        let database_name = table
+            .and_then(|table| table.database_name())
+            .map_or(database_name, Into::into);
+        let table_name = table.map_or(table_name, |table| table.name().into());
+        let location = table
+            .and_then(|table| table.storage_descriptor())
+            .and_then(|sd| sd.location())
+            .map(String::from);
 
-        let databases = response
-            .database_list()
-            .iter()
-            .map(|db| db.name().into())
-            .collect::<Vec<String>>();
+        let crawlers = paginate(|next_token| {
+            let request = GetCrawlersInput::builder().set_next_token(next_token);
+            let (database_name, table_name, location) =
+                (&database_name, &table_name, location.as_deref());
+            async move {
+                self.send_with_retry("get_table_lineage", || {
+                    self.glue().get_crawlers(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let crawlers = response
+                        .crawlers()
+                        .iter()
+                        // Crawlers touching denied databases are left out, so as not to reveal them
+                        .filter(|crawler| {
+                            crawler_databases(crawler)
+                                .all(|name| self.database_access.permits(name))
+                        })
+                        .filter_map(|crawler| {
+                            let reasons = crawler_lineage_reasons(
+                                crawler,
+                                database_name,
+                                table_name,
+                                location,
+                            );
+                            (!reasons.is_empty()).then(|| LineageSource {
+                                name: crawler.name().unwrap_or_default().into(),
+                                reasons,
+                            })
+                        })
+                        .collect::<Vec<LineageSource>>();
+                    (crawlers, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list crawlers", &e).into_mcp("get_table_lineage")
+        })?;
 
-        let result = ListDatabasesResult { databases };
-        let json_result = serde_json::to_value(result).map_err(|e| {
-            counter!("errors.list_databases.serde_error").increment(1);
-            McpError::internal_error(
-                "Failed to serialize result",
-                Some(json!({"error": e.to_string()})),
-            )
+        let jobs = paginate(|next_token| {
+            let request = GetJobsInput::builder().set_next_token(next_token);
+            let (database_name, table_name, location) =
+                (&database_name, &table_name, location.as_deref());
+            async move {
+                self.send_with_retry("get_table_lineage", || {
+                    self.glue().get_jobs(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let jobs = response
+                        .jobs()
+                        .iter()
+                        .filter(|job| {
+                            job_databases(job).all(|name| self.database_access.permits(name))
+                        })
+                        .filter_map(|job| {
+                            let reasons =
+                                job_lineage_reasons(job, database_name, table_name, location);
+                            (!reasons.is_empty()).then(|| LineageSource {
+                                name: job.name().unwrap_or_default().into(),
+                                reasons,
+                            })
+                        })
+                        .collect::<Vec<LineageSource>>();
+                    (jobs, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list jobs", &e).into_mcp("get_table_lineage")
         })?;
 
-        Ok(CallToolResult::success(vec![Content::json(json_result)?]))
+        let result = TableLineage {
+            database_name,
+            table_name,
+            location,
+            heuristic: true,
+            note: "Inferred by matching crawler targets and job arguments against the table's name, database and S3 location. Sources may be missing, e.g. jobs whose scripts hardcode their outputs, or wrong, e.g. jobs that only read the table".into(),
+            crawlers,
+            jobs,
+        };
+
+        json_result("get_table_lineage", &result)
     }
 
     #[tool(
-        description = "Get database metadata from an AWS Glue Data Catalog, including the tables in the database"
+        description = "Check whether a schema definition is already registered as a version of a schema in the AWS Glue Schema Registry, returning the matching version's ID and status, and the schema's compatibility mode"
     )]
-    async fn get_database_metadata(
+    async fn get_schema_by_definition(
         &self,
         #[tool(param)]
-        #[schemars(description = "The database name")]
-        database_name: String,
+        #[schemars(description = "The name of the registry holding the schema")]
+        registry_name: String,
+        #[tool(param)]
+        #[schemars(description = "The schema name")]
+        schema_name: String,
+        #[tool(param)]
+        #[schemars(description = "The schema definition to look for, e.g. an Avro schema as JSON")]
+        definition: String,
     ) -> Result<CallToolResult, McpError> {
-        log::info!("Getting tables for database {}", database_name);
-        counter!("calls.get_database_metadata").increment(1);
+        log::info!(
+            "Looking up a definition of schema {} in registry {}",
+            schema_name,
+            registry_name
+        );
+        counter!("calls.get_schema_by_definition").increment(1);
 
-        let response = self
-            .client
-            .get_tables()
-            .database_name(database_name.clone())
-            .send()
+        let schema_id = SchemaId::builder()
+            .registry_name(registry_name.clone())
+            .schema_name(schema_name.clone())
+            .build();
+
+        // Looked up first so that a missing schema isn't reported as a definition that doesn't match
+        let request = GetSchemaInput::builder().schema_id(schema_id.clone());
+        let schema = self
+            .send_with_retry("get_schema_by_definition", || {
+                self.glue().get_schema(request.clone())
+            })
             .await
-            .map_err(|e| {
-                counter!("errors.get_database_metadata.aws_call_error").increment(1);
-                McpError::internal_error(
-                    "Failed to get tables",
-                    Some(json!({"error": e.to_string()})),
-                )
-            })?;
+            .map_err(|e| match e.as_service_error() {
+                Some(GetSchemaError::EntityNotFoundException(_)) => GlueMcpError::not_found(
+                    format!("Schema '{schema_name}' in registry '{registry_name}' does not exist"),
+                    json!({"registry_name": registry_name, "schema_name": schema_name}),
+                ),
+                _ => GlueMcpError::from_aws("Failed to get schema", &e),
+            })
+            .map_err(|e| e.into_mcp("get_schema_by_definition"))?;
+        let compatibility = schema
+            .compatibility()
+            .map(|compatibility| compatibility.as_str().into());
 
-        let tables = response
-            .table_list()
-            .iter()
-            .map(|table| table.name().into())
-            .collect::<Vec<String>>();
+        let request = GetSchemaByDefinitionInput::builder()
+            .schema_id(schema_id)
+            .schema_definition(definition);
+        let response = self
+            .send_with_retry("get_schema_by_definition", || {
+                self.glue().get_schema_by_definition(request.clone())
+            })
+            .await;
 
-        let result = DatabaseMetadata {
-            name: database_name,
-            tables,
+        let result = match response {
+            Ok(response) => SchemaDefinitionMatch {
+                message: format!(
+                    "The definition matches version {} of schema '{}'",
+                    response.schema_version_id().unwrap_or_default(),
+                    schema_name
+                ),
+                registry_name,
+                schema_name,
+                matched: true,
+                schema_version_id: response.schema_version_id().map(Into::into),
+                status: response.status().map(|status| status.as_str().into()),
+                data_format: response
+                    .data_format()
+                    .map(|data_format| data_format.as_str().into()),
+                created_time: response.created_time().map(Into::into),
+                compatibility,
+            },
+            Err(e) if error::is_not_found(&e) => SchemaDefinitionMatch {
+                message: format!(
+                    "No version of schema '{schema_name}' matches the definition; registering it would create a new version, subject to the schema's compatibility mode"
+                ),
+                registry_name,
+                schema_name,
+                matched: false,
+                schema_version_id: None,
+                status: None,
+                data_format: None,
+                created_time: None,
+                compatibility,
+            },
+            Err(e) => {
+                return Err(
+                    GlueMcpError::from_aws("Failed to get schema by definition", &e)
+                        .into_mcp("get_schema_by_definition"),
+                );
+            }
         };
 
-        let json_result = serde_json::to_value(result).map_err(|e| {
-            counter!("errors.get_database_metadata.serde_error").increment(1);
-            McpError::internal_error(
-                "Failed to serialize result",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
-
-        Ok(CallToolResult::success(vec![Content::json(json_result)?]))
+        json_result("get_schema_by_definition", &result)
     }
 
     #[tool(
-        description = "Get table metadata from an AWS Glue Data Catalog, including the columns in the table"
+        description = "Check the syntax of an AVRO, JSON or PROTOBUF schema definition with the AWS Glue Schema Registry before registering it, returning whether it's valid and, if not, why. Nothing is registered"
     )]
-    async fn get_table_metadata(
+    async fn check_schema_version_validity(
         &self,
         #[tool(param)]
-        #[schemars(description = "The database name")]
-        database_name: String,
+        #[schemars(description = "The definition's format: AVRO, JSON or PROTOBUF")]
+        data_format: String,
         #[tool(param)]
-        #[schemars(description = "The table name")]
-        table_name: String,
+        #[schemars(description = "The schema definition to check")]
+        definition: String,
     ) -> Result<CallToolResult, McpError> {
-        log::info!("Getting columns for table {}", table_name);
-        counter!("calls.get_table_metadata").increment(1);
+        log::info!("Checking the validity of a {} schema", data_format);
+        counter!("calls.check_schema_version_validity").increment(1);
 
+        let data_format = data_format.to_uppercase();
+        let schema_data_format = DataFormat::from(data_format.as_str());
+        if let DataFormat::Unknown(_) = schema_data_format {
+            return Err(GlueMcpError::invalid_params(
+                format!("Unknown data format '{data_format}'"),
+                json!({
+                    "data_format": data_format,
+                    "valid_formats": DataFormat::values(),
+                }),
+            )
+            .into_mcp("check_schema_version_validity"));
+        }
+
+        let request = CheckSchemaVersionValidityInput::builder()
+            .data_format(schema_data_format)
+            .schema_definition(definition);
         let response = self
-            .client
-            .get_table()
-            .database_name(database_name)
-            .name(table_name.clone())
-            .send()
+            .send_with_retry("check_schema_version_validity", || {
+                self.glue().check_schema_version_validity(request.clone())
+            })
             .await
             .map_err(|e| {
-                counter!("errors.get_table_metadata.aws_call_error").increment(1);
-                McpError::internal_error(
-                    "Failed to get table metadata",
-                    Some(json!({"error": e.to_string()})),
-                )
+                GlueMcpError::from_aws("Failed to check schema validity", &e)
+                    .into_mcp("check_schema_version_validity")
             })?;
 
-        let columns = response
-            .table()
-            .and_then(|table| table.storage_descriptor())
-            .map(|sd| sd.columns())
-            .unwrap_or_default()
-            .iter()
-            .map(|col| col.name().into())
-            .collect::<Vec<String>>();
+        let result = SchemaValidity {
+            data_format,
+            valid: response.valid(),
+            error: response.error().map(Into::into),
+        };
 
-        log::info!("Got {} columns for table {}", columns.len(), table_name);
+        json_result("check_schema_version_validity", &result)
+    }
 
-        let result = TableMetadata {
-            name: table_name,
-            columns,
+    #[tool(
+        description = "Test an AWS Glue connection by having Glue reach its data source with the connection's settings and credentials, returning whether it succeeded and any error detail. Requires mutations to be enabled on the server, as it initiates network activity"
+    )]
+    async fn test_connection(
+        &self,
+        #[tool(param)]
+        #[schemars(description = "The connection name")]
+        connection_name: String,
+        #[tool(param)]
+        #[schemars(
+            description = "The ID of the Data Catalog holding the connection, defaulting to the caller's AWS account"
+        )]
+        catalog_id: Option<String>,
+    ) -> Result<CallToolResult, McpError> {
+        counter!("calls.test_connection").increment(1);
+        self.ensure_mutations_allowed("test_connection")?;
+        log::info!("Testing connection {}", connection_name);
+
+        let request = TestConnectionInput::builder()
+            .set_catalog_id(catalog_id)
+            .connection_name(connection_name.clone());
+        let response = self
+            .send_with_retry("test_connection", || {
+                self.glue().test_connection(request.clone())
+            })
+            .await;
+
+        let result = match response {
+            Ok(_) => ConnectionTestResult {
+                connection_name,
+                reachable: true,
+                status: "SUCCEEDED".into(),
+                error_code: None,
+                error_message: None,
+            },
+            Err(e) if error::is_not_found(&e) => {
+                return Err(GlueMcpError::not_found(
+                    format!("Connection '{connection_name}' does not exist"),
+                    json!({"connection_name": connection_name}),
+                )
+                .into_mcp("test_connection"));
+            }
+            Err(e) => {
+                // Throttling, missing permissions and transport failures say nothing about the
+                // connection itself, so only other errors are reported as a failed test
+                let error = GlueMcpError::from_aws("Failed to test connection", &e);
+                if e.as_service_error().is_none() || !matches!(error, GlueMcpError::Aws { .. }) {
+                    return Err(error.into_mcp("test_connection"));
+                }
+                ConnectionTestResult {
+                    connection_name,
+                    reachable: false,
+                    status: "FAILED".into(),
+                    error_code: e.code().map(Into::into),
+                    error_message: e.message().map(Into::into),
+                }
+            }
         };
 
-        let json_result = serde_json::to_value(result).map_err(|e| {
-            counter!("errors.get_table_metadata.serde_error").increment(1);
-            McpError::internal_error(
-                "Failed to serialize result",
-                Some(json!({"error": e.to_string()})),
-            )
+        json_result("test_connection", &result)
+    }
+
+    #[tool(
+        description = "List the AWS Glue machine learning transforms in the account, such as FindMatches deduplication, including each transform's ID, type and status"
+    )]
+    async fn list_ml_transforms(&self) -> Result<CallToolResult, McpError> {
+        log::info!("Listing ML transforms");
+        counter!("calls.list_ml_transforms").increment(1);
+
+        let transforms = paginate(|next_token| {
+            let request = GetMlTransformsInput::builder().set_next_token(next_token);
+            async move {
+                self.send_with_retry("list_ml_transforms", || {
+                    self.glue().get_ml_transforms(request.clone())
+                })
+                .await
+                .map(|response| {
+                    let transforms = response
+                        .transforms()
+                        .iter()
+                        .map(|transform| MlTransformSummary {
+                            id: transform.transform_id().unwrap_or_default().into(),
+                            name: transform.name().map(Into::into),
+                            transform_type: transform
+                                .parameters()
+                                .map(|parameters| parameters.transform_type().as_str().into()),
+                            status: transform.status().map(|status| status.as_str().into()),
+                            description: transform.description().map(Into::into),
+                            created_on: transform.created_on().and_then(rfc3339),
+                        })
+                        .collect::<Vec<MlTransformSummary>>();
+                    (transforms, response.next_token().map(Into::into))
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            GlueMcpError::from_aws("Failed to list ML transforms", &e)
+                .into_mcp("list_ml_transforms")
         })?;
 
-        Ok(CallToolResult::success(vec![Content::json(json_result)?]))
+        let result = ListMlTransformsResult { transforms };
+
+        record_result_size("list_ml_transforms", result.transforms.len());
+        self.list_result("list_ml_transforms", &result, "transforms")
     }
 }
 
-const_string!(Echo = "echo");
-#[tool(tool_box)]
 impl ServerHandler for GlueDataCatalog {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
+                .enable_resources()
                 .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some("This server provides a glue data catalog tool that can be used to get database and table metadata from an AWS Glue Data Catalog".to_string()),
+            instructions: Some(
+                self.instructions
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_INSTRUCTIONS.into()),
+            ),
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult {
+            next_cursor: None,
+            tools: Self::tool_box().list(),
+        })
+    }
+
+    /// Lists one database and its tables per page, so that large catalogs are listed a database
+    /// at a time. The cursor is the name of the next database to list
+    async fn list_resources(
+        &self,
+        request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let _in_flight = self.in_flight.token();
+        counter!("calls.list_resources").increment(1);
+
+        let databases = self
+            .fetch_databases("list_resources", None, None, None)
+            .await?;
+        let ListDatabasesResult { databases, .. } = serde_json::from_value(databases)
+            .map_err(|e| GlueMcpError::serialization(e).into_mcp("list_resources"))?;
+
+        let position = match request.and_then(|request| request.cursor) {
+            Some(cursor) => databases
+                .iter()
+                .position(|database_name| *database_name == cursor)
+                .ok_or_else(|| {
+                    GlueMcpError::invalid_params(
+                        format!("Invalid cursor '{cursor}'"),
+                        json!({"cursor": cursor}),
+                    )
+                    .into_mcp("list_resources")
+                })?,
+            None => 0,
+        };
+        let Some(database_name) = databases.get(position) else {
+            return Ok(ListResourcesResult {
+                next_cursor: None,
+                resources: Vec::new(),
+            });
+        };
+
+        let metadata = self
+            .fetch_database_metadata(
+                "list_resources",
+                database_name.clone(),
+                TableFilter::default(),
+                None,
+                None,
+                None,
+            )
+            .await?;
+        let DatabaseMetadata { tables, .. } = serde_json::from_value(metadata)
+            .map_err(|e| GlueMcpError::serialization(e).into_mcp("list_resources"))?;
+
+        let page = std::iter::once(resources::database_resource(database_name))
+            .chain(
+                tables
+                    .iter()
+                    .map(|table_name| resources::table_resource(database_name, table_name)),
+            )
+            .collect();
+
+        Ok(ListResourcesResult {
+            next_cursor: databases.get(position + 1).cloned(),
+            resources: page,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult {
+            next_cursor: None,
+            resource_templates: resources::resource_templates(),
+        })
+    }
+
+    /// Returns the same metadata as `get_database_metadata` or `get_table_metadata`
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let _in_flight = self.in_flight.token();
+        counter!("calls.read_resource").increment(1);
+
+        let metadata = match ResourceUri::parse(&uri) {
+            Some(ResourceUri::Database { database_name }) => {
+                self.fetch_database_metadata(
+                    "read_resource",
+                    database_name,
+                    TableFilter::default(),
+                    None,
+                    None,
+                    None,
+                )
+                .await?
+            }
+            Some(ResourceUri::Table {
+                database_name,
+                table_name,
+            }) => {
+                self.fetch_table_metadata("read_resource", database_name, table_name, None)
+                    .await?
+            }
+            None => {
+                return Err(GlueMcpError::not_found(
+                    format!(
+                        "Unknown resource '{uri}', expected glue://database/{{name}} or glue://table/{{database}}/{{name}}"
+                    ),
+                    json!({"uri": uri}),
+                )
+                .into_mcp("read_resource"));
+            }
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri,
+                mime_type: Some(resources::MIME_TYPE.into()),
+                text: metadata.to_string(),
+            }],
+        })
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Held for the duration of the call so that shutdown can wait for it to finish
+        let _in_flight = self.in_flight.token();
+
+        let request_id = new_request_id();
+        let span = tracing::info_span!(
+            "tool_call",
+            request_id = %request_id,
+            tool = %request.name,
+            database_name = Empty,
+            table_name = Empty,
+            name = Empty,
+            job_name = Empty,
+        );
+        if let Some(arguments) = &request.arguments {
+            for param in SPAN_PARAMS {
+                if let Some(value) = arguments.get(*param).and_then(Value::as_str) {
+                    span.record(*param, value);
+                }
+            }
+        }
+
+        // Checked here so that every tool taking a database name honours the allow and deny lists
+        let database_access = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("database_name"))
+            .and_then(Value::as_str)
+            .map_or(Ok(()), |database_name| {
+                self.ensure_database_allowed(&request.name, database_name)
+            });
+        if let Err(e) = database_access {
+            span.in_scope(|| tracing::warn!("Tool call rejected: {}", e.message));
+            return Err(error::with_request_id(e, &request_id));
         }
+
+        // Long listings report each page collected to clients that sent a progress token
+        let progress = progress::Progress::requested(&context);
+        progress::scope(
+            progress,
+            Self::tool_box().call(ToolCallContext::new(self, request, context)),
+        )
+        .instrument(span.clone())
+        .await
+        .map_err(|e| {
+            span.in_scope(|| tracing::warn!("Tool call failed: {}", e.message));
+            error::with_request_id(e, &request_id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glue_api::mock::MockGlue;
+
+    /// The JSON a tool returned as its first content
+    fn content_json(result: CallToolResult) -> Value {
+        let result = serde_json::to_value(result).unwrap();
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    /// The kind of failure a tool reported
+    fn error_kind(error: &McpError) -> Option<&str> {
+        error.data.as_ref()?.get("kind")?.as_str()
+    }
+
+    fn catalog(mock: &MockGlue) -> GlueDataCatalog {
+        GlueDataCatalog::builder()
+            .allow_mutations(false)
+            .build_with_api(mock.clone())
+    }
+
+    #[tokio::test]
+    async fn list_databases_includes_every_page() {
+        let mock = MockGlue::with_database_pages(&[&["analytics", "raw"], &["shared"]]);
+
+        let result = catalog(&mock)
+            .list_databases(None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let result = content_json(result);
+        assert_eq!(result["databases"], json!(["analytics", "raw", "shared"]));
+        assert_eq!(result["truncated"], false);
+        assert_eq!(mock.calls("get_databases"), 2);
+    }
+
+    #[tokio::test]
+    async fn get_table_metadata_describes_the_table() {
+        let mock = MockGlue::default().with_table(
+            "sales",
+            "orders",
+            &[("id", "bigint"), ("total", "decimal(10,2)")],
+        );
+
+        let result = catalog(&mock)
+            .get_table_metadata("sales".into(), "orders".into(), None, None, None)
+            .await
+            .unwrap();
+
+        let metadata = content_json(result);
+        assert_eq!(metadata["name"], "orders");
+        assert_eq!(metadata["location"], "s3://warehouse/sales/orders/");
+        assert_eq!(
+            metadata["columns"],
+            json!([
+                {"name": "id", "type": "bigint", "comment": null},
+                {"name": "total", "type": "decimal(10,2)", "comment": null},
+            ])
+        );
+        assert_eq!(mock.calls("get_table"), 1);
+    }
+
+    #[tokio::test]
+    async fn get_table_metadata_reports_a_missing_table_as_not_found() {
+        let mock = MockGlue::default().with_table("sales", "orders", &[]);
+
+        let error = catalog(&mock)
+            .get_table_metadata("sales".into(), "refunds".into(), None, None, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("not_found"));
+        assert!(error.message.contains("sales.refunds"));
+    }
+
+    /// Starts a job run against a mock failing every run with `error`, returning how many times
+    /// the run was requested
+    async fn start_job_run_attempts(
+        error: fn() -> SdkError<StartJobRunError, HttpResponse>,
+    ) -> usize {
+        let mock = MockGlue {
+            start_job_run_error: Some(error),
+            ..MockGlue::default()
+        };
+        let catalog = GlueDataCatalog::builder()
+            .allow_mutations(true)
+            .retry_policy(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            })
+            .build_with_api(mock.clone());
+
+        catalog
+            .start_job_run("nightly".into(), None, None)
+            .await
+            .unwrap_err();
+        mock.calls("start_job_run")
+    }
+
+    #[tokio::test]
+    async fn start_job_run_is_not_resent_after_a_timeout() {
+        assert_eq!(
+            start_job_run_attempts(|| SdkError::timeout_error("timed out")).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn start_job_run_is_resent_when_throttled() {
+        let throttled = || {
+            glue_api::mock::service_error(
+                StartJobRunError::generic(glue_api::mock::error_code("ThrottlingException")),
+                400,
+            )
+        };
+        assert_eq!(start_job_run_attempts(throttled).await, 3);
+    }
+
+    #[tokio::test]
+    async fn get_tags_rejects_arns_in_denied_databases() {
+        let catalog = GlueDataCatalog::builder()
+            .denied_databases(["*_pii"])
+            .build_with_api(MockGlue::default());
+
+        let error = catalog
+            .get_tags("arn:aws:glue:eu-west-1:123456789012:table/customers_pii/emails".into())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("access_denied"));
+    }
+
+    fn listing(count: usize) -> Value {
+        json!({
+            "databases": (0..count).map(|i| format!("database_{i:04}")).collect::<Vec<String>>(),
+            "truncated": false,
+            "next_token": "page-1",
+        })
+    }
+
+    #[test]
+    fn limit_response_size_keeps_small_results_whole() {
+        let catalog = GlueDataCatalog::builder()
+            .max_response_bytes(1024)
+            .build_with_api(MockGlue::default());
+
+        let result = catalog.limit_response_size("list_databases", listing(10), "databases");
+
+        assert_eq!(result, listing(10));
+    }
+
+    #[test]
+    fn limit_response_size_cuts_large_results_short() {
+        let catalog = GlueDataCatalog::builder()
+            .max_response_bytes(1024)
+            .build_with_api(MockGlue::default());
+
+        let result = catalog.limit_response_size("list_databases", listing(200), "databases");
+
+        let kept = result["databases"].as_array().unwrap();
+        assert!(!kept.is_empty() && kept.len() < 200);
+        assert_eq!(
+            kept[..],
+            listing(200)["databases"].as_array().unwrap()[..kept.len()]
+        );
+        assert_eq!(result["truncated"], true);
+        assert_eq!(result["next_token"], Value::Null);
+        assert!(
+            result["note"]
+                .as_str()
+                .unwrap()
+                .starts_with(&format!("Only {} of 200 databases", kept.len()))
+        );
+        assert!(result.to_string().len() <= 1024);
+    }
+
+    #[test]
+    fn limit_response_size_leaves_results_without_the_list_field_alone() {
+        let catalog = GlueDataCatalog::builder()
+            .max_response_bytes(16)
+            .build_with_api(MockGlue::default());
+        let result = json!({"description": "a description longer than the limit"});
+
+        assert_eq!(
+            catalog.limit_response_size("get_database", result.clone(), "tables"),
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn operations_the_api_lacks_fail_without_panicking() {
+        let mock = MockGlue::default();
+
+        let error = catalog(&mock).list_crawlers().await.unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("aws_call_error"));
+    }
+
+    #[test]
+    fn parse_s3_location_splits_the_bucket_and_prefix() {
+        assert_eq!(
+            parse_s3_location("s3://warehouse/sales/orders"),
+            Some(("warehouse", "sales/orders/".to_string()))
+        );
+        assert_eq!(
+            parse_s3_location("s3://warehouse/sales/orders/"),
+            Some(("warehouse", "sales/orders/".to_string()))
+        );
+        assert_eq!(
+            parse_s3_location("s3a://warehouse/sales"),
+            Some(("warehouse", "sales/".to_string()))
+        );
+        assert_eq!(
+            parse_s3_location("s3n://warehouse/sales"),
+            Some(("warehouse", "sales/".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_s3_location_accepts_a_bare_bucket() {
+        assert_eq!(
+            parse_s3_location("s3://warehouse"),
+            Some(("warehouse", String::new()))
+        );
+        assert_eq!(
+            parse_s3_location("s3://warehouse/"),
+            Some(("warehouse", String::new()))
+        );
+    }
+
+    #[test]
+    fn parse_s3_location_rejects_other_locations() {
+        assert_eq!(parse_s3_location("s3://"), None);
+        assert_eq!(parse_s3_location("s3:///sales"), None);
+        assert_eq!(parse_s3_location("hdfs://namenode/sales"), None);
+        assert_eq!(parse_s3_location("warehouse/sales"), None);
+    }
+
+    #[test]
+    fn s3_locations_overlap_when_one_contains_the_other() {
+        assert!(s3_locations_overlap(
+            "s3://warehouse/sales",
+            "s3://warehouse/sales/orders/"
+        ));
+        assert!(s3_locations_overlap(
+            "s3a://warehouse/sales/orders",
+            "s3://warehouse/sales"
+        ));
+        assert!(s3_locations_overlap(
+            "s3://warehouse/",
+            "s3://warehouse/sales"
+        ));
+        assert!(s3_locations_overlap(
+            "s3://warehouse/sales/",
+            "s3://warehouse/sales"
+        ));
+    }
+
+    #[test]
+    fn s3_locations_dont_overlap_across_buckets_or_sibling_prefixes() {
+        assert!(!s3_locations_overlap(
+            "s3://warehouse/sales",
+            "s3://archive/sales"
+        ));
+        assert!(!s3_locations_overlap(
+            "s3://warehouse/sales",
+            "s3://warehouse/sales_raw"
+        ));
+        assert!(!s3_locations_overlap(
+            "s3://warehouse/sales",
+            "hdfs://warehouse/sales"
+        ));
+    }
+
+    #[test]
+    fn catalog_arn_uses_the_partition_of_the_region() {
+        assert_eq!(
+            catalog_arn("eu-west-1", "123456789012"),
+            "arn:aws:glue:eu-west-1:123456789012:catalog"
+        );
+        assert_eq!(
+            catalog_arn("cn-north-1", "123456789012"),
+            "arn:aws-cn:glue:cn-north-1:123456789012:catalog"
+        );
+        assert_eq!(
+            catalog_arn("us-gov-west-1", "123456789012"),
+            "arn:aws-us-gov:glue:us-gov-west-1:123456789012:catalog"
+        );
+        assert_eq!(aws_partition("us-iso-east-1"), "aws-iso");
+        assert_eq!(aws_partition("us-isob-east-1"), "aws-iso-b");
+    }
+
+    #[tokio::test]
+    async fn get_column_statistics_reports_columns_glue_failed_to_read() {
+        let mock = MockGlue::default().with_table("sales", "orders", &[("id", "bigint")]);
+
+        let result = catalog(&mock)
+            .get_column_statistics(
+                "sales".into(),
+                "orders".into(),
+                vec!["id".into(), "missing".into(), "id".into(), "missing".into()],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let result = content_json(result);
+        assert_eq!(
+            result["columns"],
+            json!([{"column_name": "id", "column_type": null, "analyzed_time": null, "stats": null}])
+        );
+        assert_eq!(
+            result["failed"],
+            json!([{
+                "column_name": "missing",
+                "error_code": "EntityNotFoundException",
+                "error_message": "Column missing not found",
+            }])
+        );
+        assert_eq!(mock.calls("get_column_statistics_for_table"), 1);
+    }
+
+    #[test]
+    fn limit_response_size_only_suggests_parameters_the_tool_takes() {
+        let catalog = GlueDataCatalog::builder()
+            .max_response_bytes(1024)
+            .build_with_api(MockGlue::default());
+        let columns = json!({
+            "name": "orders",
+            "columns": (0..200).map(|i| format!("column_{i}")).collect::<Vec<String>>(),
+        });
+
+        let databases = catalog.limit_response_size("list_databases", listing(200), "databases");
+        let columns = catalog.limit_response_size("get_table_metadata", columns, "columns");
+
+        assert!(
+            databases["note"]
+                .as_str()
+                .unwrap()
+                .ends_with("To see the rest, page through them with limit and next_token")
+        );
+        assert!(
+            !columns["note"]
+                .as_str()
+                .unwrap()
+                .contains("To see the rest")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_table_optimizer_needs_a_catalog_id_when_glue_omits_it() {
+        let mock = MockGlue::default().with_table("sales", "orders", &[]);
+
+        let error = catalog(&mock)
+            .get_table_optimizer("sales".into(), "orders".into(), "compaction".into(), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("invalid_request"));
+        assert_eq!(mock.calls("get_table"), 1);
+    }
+
+    #[tokio::test]
+    async fn delete_table_is_refused_while_mutations_are_disabled() {
+        let mock = MockGlue::default().with_table("sales", "orders", &[]);
+
+        let error = catalog(&mock)
+            .delete_table("sales".into(), "orders".into(), None, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("invalid_request"));
+        assert_eq!(mock.calls("get_table"), 0);
+    }
+
+    #[tokio::test]
+    async fn delete_table_is_refused_in_denied_databases() {
+        let mock = MockGlue::default().with_table("customers_pii", "emails", &[]);
+        let catalog = GlueDataCatalog::builder()
+            .allow_mutations(true)
+            .denied_databases(["*_pii"])
+            .build_with_api(mock.clone());
+
+        let error = catalog
+            .delete_table("customers_pii".into(), "emails".into(), None, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), Some("access_denied"));
+        assert_eq!(mock.calls("get_table"), 0);
+    }
+
+    #[test]
+    fn crawler_databases_include_its_output_and_catalog_targets() {
+        use aws_sdk_glue::types::{CatalogTarget, CrawlerTargets};
+
+        let crawler = Crawler::builder()
+            .database_name("sales")
+            .targets(
+                CrawlerTargets::builder()
+                    .catalog_targets(
+                        CatalogTarget::builder()
+                            .database_name("customers_pii")
+                            .tables("emails")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        assert_eq!(
+            crawler_databases(&crawler).collect::<Vec<&str>>(),
+            ["sales", "customers_pii"]
+        );
+    }
+
+    #[test]
+    fn job_databases_come_from_database_and_table_arguments() {
+        let job = Job::builder()
+            .default_arguments("--target_database", "sales")
+            .default_arguments("--source_table", "customers_pii.emails")
+            .default_arguments("--enable-metrics", "true")
+            .build();
+
+        let mut databases = job_databases(&job).collect::<Vec<&str>>();
+        databases.sort();
+
+        assert_eq!(databases, ["customers_pii", "sales"]);
     }
 }
@@ -1,22 +1,48 @@
-mod util;
-
-use glue_mcp::GlueDataCatalog;
+use glue_mcp::util::{self, Transport};
+use std::time::Duration;
 use tracing::info;
 
 const BIND_ADDRESS: &str = "127.0.0.1:8000";
+const METRICS_ADDRESS: &str = "127.0.0.1:9000";
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     util::setup_logging();
-    util::setup_metrics();
+    let metrics_address =
+        std::env::var("GLUE_MCP_METRICS_BIND").unwrap_or_else(|_| METRICS_ADDRESS.to_string());
+    util::setup_metrics(&metrics_address)?;
 
     info!("Metrics & logging initialised");
 
-    let ct = util::start_server(BIND_ADDRESS).await?;
+    let bind_address = std::env::var("GLUE_MCP_BIND").unwrap_or_else(|_| BIND_ADDRESS.to_string());
+    let transports = std::env::var("GLUE_MCP_TRANSPORT")
+        .unwrap_or_else(|_| "sse".to_string())
+        .split(',')
+        .map(str::trim)
+        .map(|transport| match transport {
+            "sse" => Ok(Transport::Sse {
+                bind_address: bind_address.clone(),
+            }),
+            "stdio" => Ok(Transport::Stdio),
+            other => anyhow::bail!("Unknown transport '{other}', expected 'sse' or 'stdio'"),
+        })
+        .collect::<anyhow::Result<Vec<Transport>>>()?;
+    let mut server = util::start_server(&transports).await?;
 
-    tokio::signal::ctrl_c().await?;
-    info!("Shutdown signal received, stopping server");
-    ct.cancel();
+    // A local agent serving over stdio owns the process, so its disconnecting stops every transport
+    tokio::select! {
+        signal = tokio::signal::ctrl_c() => {
+            signal?;
+            info!("Shutdown signal received, stopping server");
+        }
+        () = server.stdio_closed() => info!("Client disconnected, stopping server"),
+    }
+    let drain_timeout = std::env::var("GLUE_MCP_DRAIN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map_or(DRAIN_TIMEOUT, Duration::from_secs);
+    server.shutdown(drain_timeout).await;
     info!("Server stopped");
     Ok(())
 }
@@ -0,0 +1,93 @@
+use crate::{ColumnMetadata, DatabaseMetadata, ListDatabasesResult, TableMetadata};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Renders the databases as a bulleted list
+pub(crate) fn databases(result: &ListDatabasesResult) -> String {
+    let mut markdown = String::from("## Databases\n\n");
+    push_names(&mut markdown, &result.databases, result.truncated);
+    markdown
+}
+
+/// Renders the tables of a database as a bulleted list
+pub(crate) fn database_metadata(result: &DatabaseMetadata) -> String {
+    let mut markdown = format!("## Tables in `{}`\n\n", result.name);
+    push_names(&mut markdown, &result.tables, result.truncated);
+    markdown
+}
+
+/// Renders a table's columns and partition keys as Markdown tables, followed by its storage and,
+/// for views, their SQL
+pub(crate) fn table_metadata(table: &TableMetadata) -> String {
+    let mut markdown = format!("## `{}`\n\n", table.name);
+    push_columns(&mut markdown, &table.columns);
+
+    if !table.partition_keys.is_empty() {
+        markdown.push_str("\n### Partition keys\n\n");
+        push_columns(&mut markdown, &table.partition_keys);
+    }
+
+    let storage = [
+        ("Location", &table.location),
+        ("SerDe", &table.serialization_library),
+        ("Input format", &table.input_format),
+        ("Output format", &table.output_format),
+    ];
+    if storage.iter().any(|(_, value)| value.is_some()) {
+        markdown.push_str("\n### Storage\n\n");
+        for (label, value) in storage {
+            if let Some(value) = value {
+                let _ = writeln!(markdown, "- **{label}:** `{value}`");
+            }
+        }
+    }
+
+    if !table.parameters.is_empty() {
+        markdown.push_str("\n### Parameters\n\n| Key | Value |\n| --- | --- |\n");
+        // Sorted so the output is stable between calls
+        for (key, value) in table.parameters.iter().collect::<BTreeMap<_, _>>() {
+            let _ = writeln!(markdown, "| {} | {} |", escape(key), escape(value));
+        }
+    }
+
+    if let Some(view_text) = &table.view_original_text {
+        let _ = write!(
+            markdown,
+            "\n### View definition\n\n```sql\n{view_text}\n```\n"
+        );
+    }
+
+    markdown
+}
+
+fn push_names(markdown: &mut String, names: &[String], truncated: bool) {
+    if names.is_empty() {
+        markdown.push_str("_None_\n");
+    }
+    for name in names {
+        let _ = writeln!(markdown, "- `{name}`");
+    }
+    if truncated {
+        markdown.push_str(
+            "\n_More results were omitted; raise the limit or pass the next_token to see them._\n",
+        );
+    }
+}
+
+fn push_columns(markdown: &mut String, columns: &[ColumnMetadata]) {
+    markdown.push_str("| Column | Type | Comment |\n| --- | --- | --- |\n");
+    for column in columns {
+        let _ = writeln!(
+            markdown,
+            "| {} | `{}` | {} |",
+            escape(&column.name),
+            escape(column.data_type.as_deref().unwrap_or_default()),
+            escape(column.comment.as_deref().unwrap_or_default())
+        );
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
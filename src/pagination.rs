@@ -0,0 +1,224 @@
+use crate::progress;
+use std::future::Future;
+
+/// Collects every item across all pages of a paginated Glue API call.
+///
+/// `fetch_page` is called with the continuation token from the previous page (`None` for the
+/// first page) and returns that page's items along with the next token, if any.
+pub(crate) async fn paginate<T, E, F, Fut>(fetch_page: F) -> Result<Vec<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), E>>,
+{
+    paginate_up_to(usize::MAX, fetch_page).await
+}
+
+/// Items collected from one or more pages of a paginated Glue API call
+pub(crate) struct Paginated<T> {
+    pub(crate) items: Vec<T>,
+    /// Whether more items exist beyond those collected
+    pub(crate) truncated: bool,
+    /// The token to resume from, set only when collection stopped at a page boundary with more
+    /// pages left
+    pub(crate) next_token: Option<String>,
+}
+
+/// Like [`paginate`], but stops requesting pages once `max_items` have been collected, discarding
+/// any surplus items from the final page.
+pub(crate) async fn paginate_up_to<T, E, F, Fut>(
+    max_items: usize,
+    fetch_page: F,
+) -> Result<Vec<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), E>>,
+{
+    paginate_limited(max_items, fetch_page)
+        .await
+        .map(|paginated| paginated.items)
+}
+
+/// Like [`paginate_up_to`], but also reports whether the results were truncated, i.e. whether
+/// items were discarded or further pages left unrequested, and how to resume. The running count
+/// of items is reported as progress after each page.
+pub(crate) async fn paginate_limited<T, E, F, Fut>(
+    max_items: usize,
+    mut fetch_page: F,
+) -> Result<Paginated<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), E>>,
+{
+    let mut items = Vec::new();
+    let mut next_token = None;
+    loop {
+        let (page, token) = fetch_page(next_token).await?;
+        items.extend(page);
+        progress::report(items.len()).await;
+        if items.len() >= max_items {
+            let truncated = items.len() > max_items || token.is_some();
+            // A token can't resume part way through a page, so drop it if items were discarded
+            let next_token = token.filter(|_| items.len() == max_items);
+            items.truncate(max_items);
+            return Ok(Paginated {
+                items,
+                truncated,
+                next_token,
+            });
+        }
+        match token {
+            Some(token) => next_token = Some(token),
+            None => {
+                return Ok(Paginated {
+                    items,
+                    truncated: false,
+                    next_token: None,
+                });
+            }
+        }
+    }
+}
+
+/// Fetches the single page that `token` continues from, for clients paging through results
+/// themselves
+pub(crate) async fn fetch_page_from<T, E, F, Fut>(
+    token: String,
+    mut fetch_page: F,
+) -> Result<Paginated<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), E>>,
+{
+    let (items, next_token) = fetch_page(Some(token)).await?;
+    Ok(Paginated {
+        items,
+        truncated: next_token.is_some(),
+        next_token,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GlueApi;
+    use crate::glue_api::mock::MockGlue;
+    use aws_sdk_glue::config::http::HttpResponse;
+    use aws_sdk_glue::error::SdkError;
+    use aws_sdk_glue::operation::get_databases::{GetDatabasesError, GetDatabasesInput};
+
+    type Page = (Vec<String>, Option<String>);
+
+    fn mock() -> MockGlue {
+        MockGlue::with_database_pages(&[&["a", "b"], &["c", "d"], &["e"]])
+    }
+
+    async fn database_page(
+        mock: &MockGlue,
+        next_token: Option<String>,
+    ) -> Result<Page, SdkError<GetDatabasesError, HttpResponse>> {
+        let response = mock
+            .get_databases(GetDatabasesInput::builder().set_next_token(next_token))
+            .await?;
+        let names = response
+            .database_list()
+            .iter()
+            .map(|db| db.name().to_string())
+            .collect();
+        Ok((names, response.next_token().map(Into::into)))
+    }
+
+    #[tokio::test]
+    async fn paginate_collects_every_page() {
+        let mock = mock();
+
+        let names = paginate(|token| database_page(&mock, token)).await.unwrap();
+
+        assert_eq!(names, ["a", "b", "c", "d", "e"]);
+        assert_eq!(mock.calls("get_databases"), 3);
+    }
+
+    #[tokio::test]
+    async fn paginate_up_to_stops_requesting_pages_once_enough_items_are_collected() {
+        let mock = mock();
+
+        let names = paginate_up_to(3, |token| database_page(&mock, token))
+            .await
+            .unwrap();
+
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(mock.calls("get_databases"), 2);
+    }
+
+    #[tokio::test]
+    async fn paginate_limited_resumes_from_a_page_boundary() {
+        let mock = mock();
+
+        let paginated = paginate_limited(2, |token| database_page(&mock, token))
+            .await
+            .unwrap();
+
+        assert_eq!(paginated.items, ["a", "b"]);
+        assert!(paginated.truncated);
+        assert_eq!(paginated.next_token.as_deref(), Some("page-1"));
+    }
+
+    #[tokio::test]
+    async fn paginate_limited_drops_the_token_when_items_are_discarded() {
+        let mock = mock();
+
+        let paginated = paginate_limited(3, |token| database_page(&mock, token))
+            .await
+            .unwrap();
+
+        assert_eq!(paginated.items, ["a", "b", "c"]);
+        assert!(paginated.truncated);
+        assert_eq!(paginated.next_token, None);
+    }
+
+    #[tokio::test]
+    async fn paginate_limited_is_not_truncated_when_everything_fits() {
+        let mock = mock();
+
+        let paginated = paginate_limited(5, |token| database_page(&mock, token))
+            .await
+            .unwrap();
+
+        assert_eq!(paginated.items.len(), 5);
+        assert!(!paginated.truncated);
+        assert_eq!(paginated.next_token, None);
+    }
+
+    #[tokio::test]
+    async fn fetch_page_from_fetches_only_the_page_the_token_continues_from() {
+        let mock = mock();
+
+        let paginated = fetch_page_from("page-1".into(), |token| database_page(&mock, token))
+            .await
+            .unwrap();
+
+        assert_eq!(paginated.items, ["c", "d"]);
+        assert!(paginated.truncated);
+        assert_eq!(paginated.next_token.as_deref(), Some("page-2"));
+        assert_eq!(mock.calls("get_databases"), 1);
+    }
+
+    #[tokio::test]
+    async fn paginate_stops_at_the_first_error() {
+        let mut pages = 0;
+
+        let result = paginate(|_| {
+            pages += 1;
+            let page = pages;
+            async move {
+                match page {
+                    1 => Ok((vec![1], Some("next".to_string()))),
+                    _ => Err("throttled"),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err("throttled"));
+        assert_eq!(pages, 2);
+    }
+}
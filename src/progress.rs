@@ -0,0 +1,53 @@
+use rmcp::RoleServer;
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::service::{Peer, RequestContext};
+use std::future::Future;
+
+tokio::task_local! {
+    /// Where the tool call running on this task reports its progress, if the client asked for it
+    static PROGRESS: Progress;
+}
+
+/// The client of a tool call that sent a progress token, and the token to report against
+#[derive(Clone)]
+pub(crate) struct Progress {
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+}
+
+impl Progress {
+    /// The progress wanted by the client of `context`, or `None` if its request carried no
+    /// progress token
+    pub(crate) fn requested(context: &RequestContext<RoleServer>) -> Option<Self> {
+        Some(Self {
+            peer: context.peer.clone(),
+            token: context.meta.get_progress_token()?,
+        })
+    }
+}
+
+/// Runs `future` reporting progress to `progress`, so that long pagination inside it can tell the
+/// client how far it has got. Without progress, reports made by `future` are dropped
+pub(crate) async fn scope<F: Future>(progress: Option<Progress>, future: F) -> F::Output {
+    match progress {
+        Some(progress) => PROGRESS.scope(progress, future).await,
+        None => future.await,
+    }
+}
+
+/// Tells the client of the current tool call that `items` have been collected so far, if it asked
+/// for progress. Failing to send the notification doesn't fail the call
+pub(crate) async fn report(items: usize) {
+    let Ok(Progress { peer, token }) = PROGRESS.try_with(Clone::clone) else {
+        return;
+    };
+    let notification = ProgressNotificationParam {
+        progress_token: token,
+        progress: u32::try_from(items).unwrap_or(u32::MAX),
+        total: None,
+        message: Some(format!("Collected {items} items")),
+    };
+    if let Err(e) = peer.notify_progress(notification).await {
+        log::debug!("Failed to send a progress notification: {}", e);
+    }
+}
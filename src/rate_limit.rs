@@ -0,0 +1,124 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How long a session's bucket is kept after its last request
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(600);
+
+/// The most buckets kept at once. Session IDs come from clients before they're validated, so
+/// without a bound, requests carrying made-up IDs could grow the limiter without end
+const MAX_BUCKETS: u64 = 10_000;
+
+/// A token bucket refilling at `rate` tokens per second, holding at most `burst`
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            rate,
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    /// Takes a token if one is available
+    fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let (tokens, last_refill) = &mut *state;
+        let now = Instant::now();
+        *tokens =
+            (*tokens + now.duration_since(*last_refill).as_secs_f64() * self.rate).min(self.burst);
+        *last_refill = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Limits each client session to a number of requests per second, with bursts of up to `burst`
+/// requests, so that one runaway client can't exhaust the Glue quota shared by everyone
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    buckets: moka::future::Cache<String, Arc<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: f64, burst: f64) -> Self {
+        Self {
+            requests_per_second,
+            burst,
+            buckets: moka::future::Cache::builder()
+                .time_to_idle(IDLE_BUCKET_TTL)
+                .max_capacity(MAX_BUCKETS)
+                .build(),
+        }
+    }
+
+    pub(crate) fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Whether the session or client identified by `key` may make another request now
+    pub(crate) async fn try_acquire(&self, key: &str) -> bool {
+        let (rate, burst) = (self.requests_per_second, self.burst);
+        self.buckets
+            .get_with_by_ref(key, async move { Arc::new(TokenBucket::new(rate, burst)) })
+            .await
+            .try_acquire()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_a_burst_then_rejects() {
+        let bucket = TokenBucket::new(0.001, 3.0);
+
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let bucket = TokenBucket::new(100.0, 1.0);
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(bucket.try_acquire());
+    }
+
+    #[test]
+    fn bucket_never_holds_more_than_the_burst() {
+        let bucket = TokenBucket::new(100.0, 2.0);
+
+        std::thread::sleep(Duration::from_millis(50));
+
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn limiter_keeps_a_bucket_per_session() {
+        let limiter = RateLimiter::new(0.001, 1.0);
+
+        assert!(limiter.try_acquire("session-a").await);
+        assert!(!limiter.try_acquire("session-a").await);
+        assert!(limiter.try_acquire("session-b").await);
+    }
+}
@@ -0,0 +1,84 @@
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+
+const DATABASE_PREFIX: &str = "glue://database/";
+const TABLE_PREFIX: &str = "glue://table/";
+
+/// The JSON metadata served for each resource, matching the tools' output
+pub(crate) const MIME_TYPE: &str = "application/json";
+
+/// A catalog object addressed by a `glue://` resource URI
+#[derive(Debug)]
+pub(crate) enum ResourceUri {
+    /// `glue://database/{name}`
+    Database { database_name: String },
+    /// `glue://table/{database}/{name}`
+    Table {
+        database_name: String,
+        table_name: String,
+    },
+}
+
+impl ResourceUri {
+    /// Parses a resource URI, returning `None` if it doesn't address a database or table
+    pub(crate) fn parse(uri: &str) -> Option<Self> {
+        if let Some(database_name) = uri.strip_prefix(DATABASE_PREFIX) {
+            return (!database_name.is_empty() && !database_name.contains('/')).then(|| {
+                Self::Database {
+                    database_name: database_name.into(),
+                }
+            });
+        }
+
+        let (database_name, table_name) = uri.strip_prefix(TABLE_PREFIX)?.split_once('/')?;
+        (!database_name.is_empty() && !table_name.is_empty() && !table_name.contains('/')).then(
+            || Self::Table {
+                database_name: database_name.into(),
+                table_name: table_name.into(),
+            },
+        )
+    }
+}
+
+pub(crate) fn database_resource(database_name: &str) -> Resource {
+    RawResource {
+        uri: format!("{DATABASE_PREFIX}{database_name}"),
+        name: database_name.into(),
+        description: Some(format!("The tables in the Glue database '{database_name}'")),
+        mime_type: Some(MIME_TYPE.into()),
+        size: None,
+    }
+    .no_annotation()
+}
+
+pub(crate) fn table_resource(database_name: &str, table_name: &str) -> Resource {
+    RawResource {
+        uri: format!("{TABLE_PREFIX}{database_name}/{table_name}"),
+        name: format!("{database_name}.{table_name}"),
+        description: Some(format!(
+            "The columns, partition keys and storage of the Glue table '{database_name}.{table_name}'"
+        )),
+        mime_type: Some(MIME_TYPE.into()),
+        size: None,
+    }
+    .no_annotation()
+}
+
+/// Templates for clients that construct resource URIs without listing the whole catalog
+pub(crate) fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        RawResourceTemplate {
+            uri_template: format!("{DATABASE_PREFIX}{{name}}"),
+            name: "database".into(),
+            description: Some("The tables in a Glue database".into()),
+            mime_type: Some(MIME_TYPE.into()),
+        }
+        .no_annotation(),
+        RawResourceTemplate {
+            uri_template: format!("{TABLE_PREFIX}{{database}}/{{name}}"),
+            name: "table".into(),
+            description: Some("The columns, partition keys and storage of a Glue table".into()),
+            mime_type: Some(MIME_TYPE.into()),
+        }
+        .no_annotation(),
+    ]
+}
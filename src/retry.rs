@@ -0,0 +1,218 @@
+use aws_sdk_glue::config::http::HttpResponse;
+use aws_sdk_glue::error::{ProvideErrorMetadata, SdkError};
+use metrics::counter;
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, Instant};
+
+/// Error codes Glue returns when it rejected a request for exceeding a rate limit, before acting
+/// on it
+const THROTTLING_ERROR_CODES: &[&str] = &[
+    "ThrottlingException",
+    "Throttling",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+];
+
+/// Error codes Glue returns when a request was throttled or failed transiently on the service
+/// side, and is therefore safe to retry if it doesn't change state
+const RETRYABLE_ERROR_CODES: &[&str] = &[
+    "ThrottlingException",
+    "Throttling",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+    "InternalServiceException",
+    "OperationTimeoutException",
+    "ServiceUnavailable",
+];
+
+/// How throttled and transiently failing AWS calls are retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first, before giving up
+    pub max_attempts: u32,
+    /// The delay cap for the first retry, doubling on each subsequent retry
+    pub base_delay: Duration,
+    /// The upper bound on any single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The delay before the given retry (1-based), using exponential backoff with full jitter
+    fn delay(&self, retry: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_delay);
+        let random = RandomState::new().hash_one(Instant::now());
+        exponential.mul_f64(random as f64 / u64::MAX as f64)
+    }
+}
+
+/// Sends an AWS request, retrying it with backoff while it fails with an error `retryable`
+/// accepts, such as [`is_retryable`] or [`is_throttled`]. Any other error is returned
+/// immediately.
+pub(crate) async fn send_with_retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    tool: &str,
+    retryable: fn(&SdkError<E, HttpResponse>) -> bool,
+    mut send: F,
+) -> Result<T, SdkError<E, HttpResponse>>
+where
+    E: ProvideErrorMetadata,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+{
+    let mut attempt = 1;
+    loop {
+        match send().await {
+            Err(e) if attempt < policy.max_attempts && retryable(&e) => {
+                counter!(format!("retries.{tool}")).increment(1);
+                let delay = policy.delay(attempt);
+                log::warn!(
+                    "Attempt {} of {} for {} failed ({}), retrying in {:?}",
+                    attempt,
+                    policy.max_attempts,
+                    tool,
+                    e.code().unwrap_or("no error code"),
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether a request that doesn't change state failed in a way that a retry may not, i.e. was
+/// throttled, timed out or failed transiently
+pub(crate) fn is_retryable<E: ProvideErrorMetadata>(error: &SdkError<E, HttpResponse>) -> bool {
+    match error {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(context) => {
+            context.raw().status().is_server_error()
+                || context
+                    .err()
+                    .code()
+                    .is_some_and(|code| RETRYABLE_ERROR_CODES.contains(&code))
+        }
+        _ => false,
+    }
+}
+
+/// Whether Glue rejected a request for exceeding a rate limit. Unlike other failures, this shows
+/// the request wasn't acted on, so it's the only one a request that changes state can be retried
+/// on: after a timeout, for instance, a job run may have been started all the same
+pub(crate) fn is_throttled<E: ProvideErrorMetadata>(error: &SdkError<E, HttpResponse>) -> bool {
+    match error {
+        SdkError::ServiceError(context) => context
+            .err()
+            .code()
+            .is_some_and(|code| THROTTLING_ERROR_CODES.contains(&code)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_glue::error::ErrorMetadata;
+    use aws_sdk_glue::operation::start_job_run::StartJobRunError;
+    use aws_smithy_types::body::SdkBody;
+    use std::cell::Cell;
+
+    type Error = SdkError<StartJobRunError, HttpResponse>;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    fn service_error(code: &str, status: u16) -> Error {
+        SdkError::service_error(
+            StartJobRunError::generic(ErrorMetadata::builder().code(code).build()),
+            HttpResponse::new(status.try_into().unwrap(), SdkBody::empty()),
+        )
+    }
+
+    fn throttled() -> Error {
+        service_error("ThrottlingException", 400)
+    }
+
+    fn internal_error() -> Error {
+        service_error("InternalServiceException", 500)
+    }
+
+    fn not_found() -> Error {
+        service_error("EntityNotFoundException", 400)
+    }
+
+    fn timed_out() -> Error {
+        SdkError::timeout_error("timed out")
+    }
+
+    /// Sends a request failing with `error` every time, returning how many attempts were made
+    async fn attempts(retryable: fn(&Error) -> bool, error: fn() -> Error) -> u32 {
+        let attempts = Cell::new(0);
+        let result: Result<(), Error> = send_with_retry(&POLICY, "test", retryable, || {
+            attempts.set(attempts.get() + 1);
+            async move { Err(error()) }
+        })
+        .await;
+        assert!(result.is_err());
+        attempts.get()
+    }
+
+    #[tokio::test]
+    async fn retries_reads_on_transient_failures() {
+        assert_eq!(attempts(is_retryable, throttled).await, 3);
+        assert_eq!(attempts(is_retryable, internal_error).await, 3);
+        assert_eq!(attempts(is_retryable, timed_out).await, 3);
+    }
+
+    #[tokio::test]
+    async fn retries_mutations_only_when_throttled() {
+        assert_eq!(attempts(is_throttled, throttled).await, 3);
+        assert_eq!(attempts(is_throttled, internal_error).await, 1);
+        assert_eq!(attempts(is_throttled, timed_out).await, 1);
+    }
+
+    #[tokio::test]
+    async fn fails_immediately_on_other_errors() {
+        assert_eq!(attempts(is_retryable, not_found).await, 1);
+        assert_eq!(attempts(is_throttled, not_found).await, 1);
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_once_the_request_succeeds() {
+        let attempts = Cell::new(0);
+        let result = send_with_retry(&POLICY, "test", is_retryable, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt == 1 {
+                    Err(throttled())
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.get(), 2);
+    }
+}
@@ -1,45 +1,508 @@
-use crate::GlueDataCatalog;
+use crate::rate_limit::RateLimiter;
+use crate::{GlueDataCatalog, RetryPolicy};
+use anyhow::Context;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use axum_server::tls_rustls::RustlsConfig;
+use metrics::counter;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use metrics_util::MetricKindMask;
-use rmcp::transport::sse_server::SseServer;
+use rmcp::ServiceExt;
+use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use rmcp::transport::stdio;
+use serde_json::{Value, json};
 use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{Level, info};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{Level, error, info, warn};
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
 
-/// Sets up logging with tracing
+/// How long `/ready` waits for AWS Glue before reporting the server as unavailable
+const READY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sets up logging with tracing. Logs are written to stderr so they never interleave with the
+/// MCP messages exchanged over stdout when serving the stdio transport. Records from the `log`
+/// crate are forwarded to tracing so that they carry the context of the tool call's span. Logs
+/// are human-readable unless `GLUE_MCP_LOG_FORMAT=json`, which writes one JSON object per event,
+/// including its spans, for log pipelines to parse
 pub fn setup_logging() {
-    let subscriber = FmtSubscriber::builder()
+    tracing_log::LogTracer::init().expect("Failed to forward log records to tracing");
+    let builder = FmtSubscriber::builder()
         .with_env_filter(EnvFilter::from_default_env())
         .with_max_level(Level::INFO)
-        .finish();
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
+        .with_writer(std::io::stderr);
+    let json = std::env::var("GLUE_MCP_LOG_FORMAT")
+        .is_ok_and(|format| format.eq_ignore_ascii_case("json"));
+    let result = if json {
+        tracing::subscriber::set_global_default(builder.json().finish())
+    } else {
+        tracing::subscriber::set_global_default(builder.finish())
+    };
+    result.expect("Failed to set tracing subscriber");
 }
 
-pub fn setup_metrics() {
-    let builder = PrometheusBuilder::new();
-    builder
+/// Installs the Prometheus recorder and serves the metrics for scraping on `listen_address`
+pub fn setup_metrics(listen_address: &str) -> anyhow::Result<()> {
+    let addr: SocketAddr = listen_address.parse().with_context(|| {
+        format!("Invalid metrics address '{listen_address}', expected an address like 0.0.0.0:9000")
+    })?;
+    PrometheusBuilder::new()
+        .with_http_listener(addr)
         .idle_timeout(
             MetricKindMask::COUNTER | MetricKindMask::HISTOGRAM,
             Some(Duration::from_secs(60)),
         )
-        .install()
-        .expect("failed to install Prometheus recorder");
+        .install()?;
+    info!("Serving Prometheus metrics on {}", addr);
+    Ok(())
+}
+
+/// Builds the GlueDataCatalog served by the binary, applying the optional settings read from the
+/// environment:
+///
+/// * `GLUE_MCP_ENDPOINT_URL` sends Glue requests to a custom endpoint, e.g. LocalStack
+/// * `GLUE_MCP_CACHE_TTL_SECS` enables caching of catalog reads for the given number of seconds
+/// * `GLUE_MCP_MAX_ATTEMPTS` sets how many times a throttled AWS call is attempted
+/// * `GLUE_MCP_REQUEST_TIMEOUT_SECS` sets how long a single AWS request may take
+/// * `GLUE_MCP_MAX_CONCURRENT_REQUESTS` bounds how many AWS requests are in flight at once
+/// * `GLUE_MCP_MAX_RESPONSE_BYTES` sets the largest response a listing tool returns
+/// * `GLUE_MCP_CASE_INSENSITIVE_NAMES` resolves database and table names ignoring case
+/// * `GLUE_MCP_ALLOWED_DATABASES` and `GLUE_MCP_DENIED_DATABASES` restrict the databases the
+///   server touches, each a comma-separated list of glob patterns
+/// * `GLUE_MCP_INSTRUCTIONS` replaces the instructions sent to clients on initialization
+async fn catalog_from_env() -> anyhow::Result<GlueDataCatalog> {
+    let mut builder = GlueDataCatalog::builder();
+
+    if let Some(endpoint_url) = std::env::var("GLUE_MCP_ENDPOINT_URL")
+        .ok()
+        .filter(|endpoint_url| !endpoint_url.is_empty())
+    {
+        info!("Sending Glue requests to {}", endpoint_url);
+        builder = builder.endpoint_url(endpoint_url);
+    }
+
+    if let Some(ttl) = env_var::<u64>("GLUE_MCP_CACHE_TTL_SECS") {
+        info!("Caching catalog reads for {} seconds", ttl);
+        builder = builder.cache_ttl(Duration::from_secs(ttl));
+    }
+
+    if let Some(max_attempts) = env_var::<u32>("GLUE_MCP_MAX_ATTEMPTS") {
+        info!(
+            "Attempting throttled AWS calls up to {} times",
+            max_attempts
+        );
+        builder = builder.retry_policy(RetryPolicy {
+            max_attempts,
+            ..RetryPolicy::default()
+        });
+    }
+
+    if let Some(timeout) = env_var::<u64>("GLUE_MCP_REQUEST_TIMEOUT_SECS") {
+        info!("Timing out AWS requests after {} seconds", timeout);
+        builder = builder.request_timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(max_concurrent_requests) = env_var::<usize>("GLUE_MCP_MAX_CONCURRENT_REQUESTS") {
+        info!(
+            "Sending at most {} concurrent AWS requests",
+            max_concurrent_requests
+        );
+        builder = builder.max_concurrent_requests(max_concurrent_requests);
+    }
+
+    if let Some(max_response_bytes) = env_var::<usize>("GLUE_MCP_MAX_RESPONSE_BYTES") {
+        info!(
+            "Cutting listing responses short at {} bytes",
+            max_response_bytes
+        );
+        builder = builder.max_response_bytes(max_response_bytes);
+    }
+
+    if env_var::<bool>("GLUE_MCP_CASE_INSENSITIVE_NAMES").unwrap_or(false) {
+        info!("Resolving database and table names ignoring case");
+        builder = builder.case_insensitive_names(true);
+    }
+
+    if let Some(patterns) = env_list("GLUE_MCP_ALLOWED_DATABASES") {
+        info!("Only allowing databases matching {}", patterns.join(", "));
+        builder = builder.allowed_databases(patterns);
+    }
+
+    if let Some(patterns) = env_list("GLUE_MCP_DENIED_DATABASES") {
+        info!("Denying databases matching {}", patterns.join(", "));
+        builder = builder.denied_databases(patterns);
+    }
+
+    if let Some(instructions) = std::env::var("GLUE_MCP_INSTRUCTIONS")
+        .ok()
+        .filter(|instructions| !instructions.is_empty())
+    {
+        info!("Sending custom instructions to clients");
+        builder = builder.instructions(instructions);
+    }
+
+    builder.build().await
+}
+
+/// Reads and parses an optional environment variable, ignoring values that fail to parse
+fn env_var<T: FromStr>(name: &str) -> Option<T> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+}
+
+/// Reads an optional comma-separated environment variable, ignoring empty entries
+fn env_list(name: &str) -> Option<Vec<String>> {
+    let values = std::env::var(name)
+        .ok()?
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(Into::into)
+        .collect::<Vec<String>>();
+    (!values.is_empty()).then_some(values)
+}
+
+/// A transport the MCP server can be reached over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// SSE over HTTP, listening on `bind_address` alongside the health probes
+    Sse { bind_address: String },
+    /// stdin/stdout, for a client that launched the server itself
+    Stdio,
+}
+
+/// Starts serving one shared GlueDataCatalog over each of `transports` at once, so that they
+/// share its AWS clients, cache and limits. At most one may be [`Transport::Stdio`]. When
+/// `GLUE_MCP_AUTH_TOKEN` is set, MCP requests over SSE must present it as a bearer token, while
+/// the probes stay open. When `GLUE_MCP_PREFETCH_DATABASES` is set, the database listing is
+/// cached before serving starts
+pub async fn start_server(transports: &[Transport]) -> anyhow::Result<ServerHandle> {
+    anyhow::ensure!(!transports.is_empty(), "No transports to serve");
+    anyhow::ensure!(
+        transports
+            .iter()
+            .filter(|transport| **transport == Transport::Stdio)
+            .count()
+            <= 1,
+        "The stdio transport can only be served once"
+    );
+
+    let auth_token = std::env::var("GLUE_MCP_AUTH_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty());
+    let catalog = catalog_from_env()
+        .await?
+        .with_auth_required(auth_token.is_some());
+
+    if env_var::<bool>("GLUE_MCP_PREFETCH_DATABASES").unwrap_or(false) {
+        // Failing to prefetch only costs the first client the latency it would have had anyway
+        match catalog.prefetch_databases().await {
+            Ok(Some(count)) => info!("Prefetched {} databases into the cache", count),
+            Ok(None) => warn!("Not prefetching databases, as caching is disabled"),
+            Err(e) => warn!("{:#}", e),
+        }
+    }
+
+    let ct = CancellationToken::new();
+    // A child token so that new connections can be refused while existing sessions keep running
+    let listener_ct = ct.child_token();
+    let mut stdio_session = None;
+    for transport in transports {
+        match transport {
+            Transport::Sse { bind_address } => {
+                serve_sse(
+                    &catalog,
+                    bind_address,
+                    auth_token.clone(),
+                    &ct,
+                    &listener_ct,
+                )
+                .await?;
+            }
+            Transport::Stdio => {
+                info!("Starting server on stdio");
+                let service = catalog.clone();
+                let session_ct = ct.child_token();
+                // Spawned straight away, as serving waits for the client to initialize
+                stdio_session = Some(tokio::spawn(async move {
+                    match service.serve_with_ct(stdio(), session_ct).await {
+                        Ok(server) => {
+                            if let Err(e) = server.waiting().await {
+                                error!("Stdio session error: {}", e);
+                            }
+                        }
+                        Err(e) => error!("Failed to start the stdio session: {}", e),
+                    }
+                }));
+            }
+        }
+    }
+
+    Ok(ServerHandle {
+        ct,
+        listener_ct,
+        catalog,
+        stdio_session,
+    })
 }
 
-/// Starts the SSE server with the GlueDataCatalog service
-pub async fn start_server(
+/// Serves `catalog` over SSE on `bind_address`, with the `/health` and `/ready` probes on the same
+/// address. When `GLUE_MCP_RATE_LIMIT_RPS` is set, each session is limited to that many MCP
+/// requests per second. When `GLUE_MCP_TLS_CERT` and `GLUE_MCP_TLS_KEY` are set, everything is
+/// served over HTTPS. The listener stops on `listener_ct`, and sessions on `ct`
+async fn serve_sse(
+    catalog: &GlueDataCatalog,
     bind_address: &str,
-) -> anyhow::Result<tokio_util::sync::CancellationToken> {
-    // Log server startup
+    auth_token: Option<String>,
+    ct: &CancellationToken,
+    listener_ct: &CancellationToken,
+) -> anyhow::Result<()> {
     info!("Starting server on {}", bind_address);
 
-    let service = GlueDataCatalog::from_env().await;
-    let addr: SocketAddr = bind_address.parse()?;
+    let addr: SocketAddr = bind_address.parse().with_context(|| {
+        format!("Invalid bind address '{bind_address}', expected an address like 0.0.0.0:8000")
+    })?;
 
-    let ct = SseServer::serve(addr)
-        .await?
-        .with_service(move || service.clone());
+    let (sse_server, sse_router) = SseServer::new(SseServerConfig {
+        bind: addr,
+        sse_path: "/sse".to_string(),
+        post_path: "/message".to_string(),
+        ct: ct.clone(),
+        sse_keep_alive: None,
+    });
+    let sse_router = match env_var::<f64>("GLUE_MCP_RATE_LIMIT_RPS").filter(|rps| *rps > 0.0) {
+        Some(requests_per_second) => {
+            let burst = env_var::<f64>("GLUE_MCP_RATE_LIMIT_BURST")
+                .unwrap_or(requests_per_second)
+                .max(1.0);
+            info!(
+                "Limiting each session to {} requests per second, in bursts of up to {}",
+                requests_per_second, burst
+            );
+            sse_router.layer(middleware::from_fn_with_state(
+                RateLimiter::new(requests_per_second, burst),
+                enforce_rate_limit,
+            ))
+        }
+        None => sse_router,
+    };
+    let sse_router = match auth_token {
+        Some(token) => {
+            info!("Requiring bearer token authentication for MCP requests");
+            sse_router.layer(middleware::from_fn_with_state(
+                Arc::<str>::from(token),
+                require_bearer_token,
+            ))
+        }
+        None => sse_router,
+    };
+    let router = sse_router.merge(health_router(catalog.clone()));
+
+    let shutdown = listener_ct.clone();
+    match tls_config_from_env().await? {
+        Some(tls_config) => {
+            info!("Serving over TLS");
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                shutdown.cancelled().await;
+                shutdown_handle.graceful_shutdown(None);
+            });
+            tokio::spawn(async move {
+                let server = axum_server::bind_rustls(addr, tls_config)
+                    .handle(handle)
+                    .serve(router.into_make_service_with_connect_info::<SocketAddr>());
+                if let Err(e) = server.await {
+                    error!("Server error: {}", e);
+                }
+            });
+        }
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            tokio::spawn(async move {
+                let server = axum::serve(
+                    listener,
+                    router.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .with_graceful_shutdown(async move { shutdown.cancelled().await });
+                if let Err(e) = server.await {
+                    error!("Server error: {}", e);
+                }
+            });
+        }
+    }
+
+    let service = catalog.clone();
+    sse_server.with_service(move || service.clone());
+
+    Ok(())
+}
+
+/// The running transports, which can be shut down together gracefully
+pub struct ServerHandle {
+    ct: CancellationToken,
+    listener_ct: CancellationToken,
+    catalog: GlueDataCatalog,
+    stdio_session: Option<JoinHandle<()>>,
+}
+
+impl ServerHandle {
+    /// Waits for the stdio client to disconnect, or forever if stdio isn't served
+    pub async fn stdio_closed(&mut self) {
+        match self.stdio_session.take() {
+            Some(session) => {
+                let _ = session.await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Stops accepting new SSE connections, waits up to `drain_timeout` for in-flight tool calls
+    /// to finish, then cancels every remaining session, including the stdio one
+    pub async fn shutdown(self, drain_timeout: Duration) {
+        self.listener_ct.cancel();
+
+        let in_flight = self.catalog.in_flight_calls();
+        if in_flight > 0 {
+            info!(
+                "Waiting up to {:?} for {} in-flight tool calls to finish",
+                drain_timeout, in_flight
+            );
+            let remaining = self.catalog.drain(drain_timeout).await;
+            if remaining > 0 {
+                warn!(
+                    "{} tool calls were still in flight at shutdown and will be cancelled",
+                    remaining
+                );
+            }
+        }
+
+        self.ct.cancel();
+    }
+}
+
+/// Loads the TLS certificate chain and private key named by `GLUE_MCP_TLS_CERT` and
+/// `GLUE_MCP_TLS_KEY`, returning `None` to serve plaintext when neither is set
+async fn tls_config_from_env() -> anyhow::Result<Option<RustlsConfig>> {
+    match (
+        std::env::var("GLUE_MCP_TLS_CERT").ok(),
+        std::env::var("GLUE_MCP_TLS_KEY").ok(),
+    ) {
+        (Some(cert), Some(key)) => {
+            let config = RustlsConfig::from_pem_file(&cert, &key)
+                .await
+                .with_context(|| {
+                    format!("Failed to load TLS certificate '{cert}' and private key '{key}'")
+                })?;
+            Ok(Some(config))
+        }
+        (None, None) => Ok(None),
+        (Some(_), None) => anyhow::bail!(
+            "GLUE_MCP_TLS_CERT is set but GLUE_MCP_TLS_KEY is not; both are required to enable TLS"
+        ),
+        (None, Some(_)) => anyhow::bail!(
+            "GLUE_MCP_TLS_KEY is set but GLUE_MCP_TLS_CERT is not; both are required to enable TLS"
+        ),
+    }
+}
+
+/// Rejects requests whose `Authorization: Bearer` header doesn't carry the expected token
+async fn require_bearer_token(
+    State(expected): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()));
+
+    if authorized {
+        next.run(request).await
+    } else {
+        counter!("auth.rejected").increment(1);
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+/// Rejects requests beyond the session's rate limit with `429 Too Many Requests`. Requests are
+/// attributed to the session in the `sessionId` query parameter of MCP messages, and any others,
+/// such as new SSE connections, to the client's IP address, so that one client reconnecting in a
+/// loop can't lock everyone else out
+async fn enforce_rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let session_id = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("sessionId="))
+    });
+    let key = match session_id {
+        Some(session_id) => format!("session:{session_id}"),
+        None => match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(peer)) => format!("peer:{}", peer.ip()),
+            None => String::new(),
+        },
+    };
+
+    if limiter.try_acquire(&key).await {
+        next.run(request).await
+    } else {
+        counter!("errors.rate_limited").increment(1);
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "error": format!(
+                    "Rate limit of {} requests per second exceeded; retry shortly",
+                    limiter.requests_per_second()
+                ),
+                "kind": "rate_limited",
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Compares without short-circuiting so response timing doesn't reveal how much of a token matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Routes for liveness (`/health`) and readiness (`/ready`) probes
+fn health_router(catalog: GlueDataCatalog) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .with_state(catalog)
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({"status": "ok"}))
+}
 
-    Ok(ct)
+/// Reports ready only if Glue answers a lightweight request within [`READY_TIMEOUT`]
+async fn ready(State(catalog): State<GlueDataCatalog>) -> (StatusCode, Json<Value>) {
+    match tokio::time::timeout(READY_TIMEOUT, catalog.ping()).await {
+        Ok(Ok(())) => (StatusCode::OK, Json(json!({"status": "ready"}))),
+        Ok(Err(e)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"status": "unavailable", "error": e.to_string()})),
+        ),
+        Err(_) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"status": "unavailable", "error": "timed out waiting for AWS Glue"})),
+        ),
+    }
 }